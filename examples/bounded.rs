@@ -0,0 +1,176 @@
+//! Example of a session consisting of two processes connected by a bounded
+//! 'Simplex' channel.
+//!
+//! The producer is an 'Isochronous' (timed, polling) process with 1ms tick
+//! length that attempts to send a character on every update with `try_send`.
+//! The consumer is an 'Asynchronous' process that takes 5ms to handle each
+//! message, so the channel quickly fills to capacity and the producer sheds
+//! load by dropping characters instead of blocking or growing the queue.
+//!
+//! Running this example will produce a DOT file representing the data flow
+//! diagram of the session. To create a PNG image from the generated DOT file:
+//!
+//! ```bash
+//! make -f MakefileDot bounded
+//! ```
+
+#![allow(dead_code)]
+
+use colored;
+use env_logger;
+use log;
+
+use apis;
+
+///////////////////////////////////////////////////////////////////////////////
+//  constants                                                                //
+///////////////////////////////////////////////////////////////////////////////
+
+//  Off, Error, Warn, Info, Debug, Trace
+pub const LOG_LEVEL : log::LevelFilter = log::LevelFilter::Info;
+
+pub const CAPACITY    : usize = 4;
+pub const MAX_UPDATES : u64   = 200;
+
+///////////////////////////////////////////////////////////////////////////////
+//  session                                                                  //
+///////////////////////////////////////////////////////////////////////////////
+
+apis::def_session! {
+  context ChargenUpcaseBounded {
+    PROCESSES where
+      let process    = self,
+      let message_in = message_in
+    [
+      process Chargen (update_count : u64, dropped : u64) -> (u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (1),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
+        }
+        sourcepoints   [Charstream]
+        endpoints      []
+        handle_message { unreachable!() }
+        update         { process.chargen_update() }
+      }
+      process Upcase (history : String) -> (String) {
+        kind           { apis::process::Kind::asynchronous_default() }
+        sourcepoints   []
+        endpoints      [Charstream]
+        handle_message { process.upcase_handle_message (message_in) }
+        update         { apis::process::ControlFlow::Continue }
+      }
+    ]
+    CHANNELS  [
+      channel Charstream <Charstreammessage> (Simplex) {
+        producers [Chargen]
+        consumers [Upcase]
+        capacity  { CAPACITY }
+      }
+    ]
+    MESSAGES [
+      message Charstreammessage {
+        Achar (char),
+        Quit
+      }
+    ]
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls                                                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+impl Chargen {
+  fn chargen_update (&mut self) -> apis::process::ControlFlow {
+    use apis::{channel, Process};
+    log::trace!("chargen update...");
+    let mut result = apis::process::ControlFlow::Continue;
+    self.update_count += 1;
+    let ch = char::from (b'a' + u8::try_from (self.update_count % 26).unwrap());
+    match self.try_send_typed (channels::Charstream, Charstreammessage::Achar (ch)) {
+      Ok (()) => {}
+      Err (channel::TrySendError::Full (_)) => self.dropped += 1,
+      Err (
+        channel::TrySendError::Disconnected (_)
+        | channel::TrySendError::Protocol (_)
+        | channel::TrySendError::WrongAddressing (_)
+      ) => result = apis::process::ControlFlow::Break
+    }
+    if self.update_count == MAX_UPDATES {
+      // blocks until there is room for the final message
+      let _ = self.send_typed (channels::Charstream, Charstreammessage::Quit);
+      println!("chargen dropped {} of {} characters", self.dropped, MAX_UPDATES);
+      self.result = self.dropped;
+      result = apis::process::ControlFlow::Break;
+    }
+    log::trace!("...chargen update");
+    result
+  }
+}
+// end impl Chargen
+
+impl Upcase {
+  fn upcase_handle_message (&mut self, message : GlobalMessage)
+    -> apis::process::ControlFlow
+  {
+    log::trace!("upcase handle message...");
+    let mut result = apis::process::ControlFlow::Continue;
+    match message {
+      GlobalMessage::Charstreammessage (charstreammessage) => {
+        match charstreammessage {
+          Charstreammessage::Quit => {
+            println!("upcase history final: {}", self.history);
+            self.result = self.history.clone();
+            result = apis::process::ControlFlow::Break
+          }
+          Charstreammessage::Achar (ch) => {
+            // a slow consumer
+            std::thread::sleep (std::time::Duration::from_millis (5));
+            self.history.push (ch.to_uppercase().next().unwrap());
+          }
+        }
+      }
+    }
+    log::trace!("...upcase handle message");
+    result
+  }
+}
+// end impl Upcase
+
+///////////////////////////////////////////////////////////////////////////////
+//  main                                                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+fn main() {
+  use std::io::Write;
+  use colored::Colorize;
+  use apis::session::Context;
+
+  let example_name = std::path::PathBuf::from (std::env::args().next().unwrap())
+    .file_name().unwrap().to_str().unwrap().to_string();
+
+  println!("{}", format!("{example_name} main...").green().bold());
+
+  env_logger::Builder::new()
+    .filter_level (LOG_LEVEL)
+    .parse_default_env()
+    .init();
+
+  // here is where we find out if the session definition has any errors
+  let session_def = ChargenUpcaseBounded::def().unwrap();
+  // create a dotfile for the session
+  let mut f = std::fs::File::create (format!("{example_name}.dot")).unwrap();
+  f.write_all (session_def.dotfile_show_defaults().as_bytes()).unwrap();
+  drop (f);
+  // create the session from the definition
+  let mut session : apis::Session <ChargenUpcaseBounded> = session_def.into();
+  // run to completion
+  let results = session.run();
+  println!("results: {results:?}");
+
+  println!("{}", format!("...{example_name} main").green().bold());
+}
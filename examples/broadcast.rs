@@ -0,0 +1,173 @@
+//! Example of a session consisting of one sender and three receivers connected
+//! by a 'Broadcast' channel.
+//!
+//! The producer is an 'Isochronous' (timed, polling) process with a 20ms tick
+//! length that will broadcast a randomly generated integer to every consumer on
+//! each update. The consumers are 'Asynchronous' processes that will add received
+//! integers to a local 'sum' with different initial values, so the final sums
+//! should all differ by the same amounts as the initial values.
+//!
+//! Running this example will produce a DOT file representing the data flow
+//! diagram of the session. To create a PNG image from the generated DOT file:
+//!
+//! ```bash
+//! make -f MakefileDot broadcast
+//! ```
+
+#![allow(dead_code)]
+
+use colored;
+use env_logger;
+use log;
+use rand;
+
+use apis;
+
+////////////////////////////////////////////////////////////////////////////////
+//  constants                                                                 //
+////////////////////////////////////////////////////////////////////////////////
+
+//  Off, Error, Warn, Info, Debug, Trace
+pub const LOG_LEVEL : log::LevelFilter = log::LevelFilter::Info;
+
+////////////////////////////////////////////////////////////////////////////////
+//  session                                                                   //
+////////////////////////////////////////////////////////////////////////////////
+
+apis::def_session! {
+  context RandBroadcast {
+    PROCESSES where
+      let process    = self,
+      let message_in = message_in
+    [
+      process RandGen (update_count : u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (20),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
+        }
+        sourcepoints   [Randints]
+        endpoints      []
+        handle_message { unreachable!() }
+        update {
+          use rand::RngExt;
+          let mut rng = rand::rng();
+          let rand_int = rng.random_range (1..100);
+          let send_result = process.send (
+            ChannelId::Randints, Randintsmessage::Anint (rand_int));
+          process.update_count += 1;
+          if send_result.is_err() || 50 <= process.update_count {
+            // a single send reaches every consumer
+            let _ = process.send (ChannelId::Randints, Randintsmessage::Quit);
+            apis::process::ControlFlow::Break
+          } else {
+            apis::process::ControlFlow::Continue
+          }
+        }
+      }
+      process Sum1 (sum : i64 = 0) -> (i64) {
+        kind           { apis::process::Kind::asynchronous_default() }
+        sourcepoints   []
+        endpoints      [Randints]
+        terminate      { process.result = process.sum; }
+        handle_message { process.sum_handle_message (message_in) }
+        update         { apis::process::ControlFlow::Continue }
+      }
+      process Sum2 (sum : i64 = -100) -> (i64) {
+        kind           { apis::process::Kind::asynchronous_default() }
+        sourcepoints   []
+        endpoints      [Randints]
+        terminate      { process.result = process.sum; }
+        handle_message { process.sum_handle_message (message_in) }
+        update         { apis::process::ControlFlow::Continue }
+      }
+      process Sum3 (sum : i64 = 100) -> (i64) {
+        kind           { apis::process::Kind::asynchronous_default() }
+        sourcepoints   []
+        endpoints      [Randints]
+        terminate      { process.result = process.sum; }
+        handle_message { process.sum_handle_message (message_in) }
+        update         { apis::process::ControlFlow::Continue }
+      }
+    ]
+    CHANNELS  [
+      channel Randints <Randintsmessage> (Broadcast) {
+        producers [RandGen]
+        consumers [Sum1, Sum2, Sum3]
+      }
+    ]
+    MESSAGES [
+      #[derive(Clone)]
+      message Randintsmessage {
+        Anint (i64),
+        Quit
+      }
+    ]
+  }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+//  impls                                                                     //
+////////////////////////////////////////////////////////////////////////////////
+
+macro_rules! impl_sum {
+  ($process:ident) => {
+    impl $process {
+      fn sum_handle_message (&mut self, message : GlobalMessage)
+        -> apis::process::ControlFlow
+      {
+        match message {
+          GlobalMessage::Randintsmessage (Randintsmessage::Quit) => {
+            println!("{} final: {}", stringify!($process), self.sum);
+            apis::process::ControlFlow::Break
+          }
+          GlobalMessage::Randintsmessage (Randintsmessage::Anint (anint)) => {
+            self.sum += anint;
+            apis::process::ControlFlow::Continue
+          }
+        }
+      }
+    }
+  }
+}
+
+impl_sum!(Sum1);
+impl_sum!(Sum2);
+impl_sum!(Sum3);
+
+////////////////////////////////////////////////////////////////////////////////
+//  main                                                                      //
+////////////////////////////////////////////////////////////////////////////////
+
+fn main() {
+  use std::io::Write;
+  use colored::Colorize;
+  use apis::session::Context;
+
+  let example_name = std::path::PathBuf::from (std::env::args().next().unwrap())
+    .file_name().unwrap().to_str().unwrap().to_string();
+
+  println!("{}", format!("{example_name} main...").green().bold());
+
+  env_logger::Builder::new()
+    .filter_level (LOG_LEVEL)
+    .parse_default_env()
+    .init();
+
+  // here is where we find out if the session definition has any errors
+  let session_def = RandBroadcast::def().unwrap();
+  // create a dotfile for the session
+  let mut f = std::fs::File::create (format!("{example_name}.dot")).unwrap();
+  f.write_all (session_def.dotfile_show_defaults().as_bytes()).unwrap();
+  drop (f);
+  // create the session from the definition
+  let mut session : apis::Session <RandBroadcast> = session_def.into();
+  // run to completion
+  let results = session.run();
+  println!("results: {results:?}");
+
+  println!("{}", format!("...{example_name} main").green().bold());
+}
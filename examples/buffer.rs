@@ -0,0 +1,155 @@
+//! Example of a session consisting of two processes connected by a 'Buffer'
+//! channel.
+//!
+//! The producer is an 'Isochronous' (timed, polling) process with 1ms tick
+//! length that sends a snapshot of its state on every update. The consumer is
+//! an 'Asynchronous' process that takes 10ms to render each snapshot, so most
+//! snapshots are overwritten before they are received and the consumer only
+//! ever renders the most recent state.
+//!
+//! Running this example will produce a DOT file representing the data flow
+//! diagram of the session. To create a PNG image from the generated DOT file:
+//!
+//! ```bash
+//! make -f MakefileDot buffer
+//! ```
+
+#![allow(dead_code)]
+
+use colored;
+use env_logger;
+use log;
+
+use apis;
+
+///////////////////////////////////////////////////////////////////////////////
+//  constants                                                                //
+///////////////////////////////////////////////////////////////////////////////
+
+//  Off, Error, Warn, Info, Debug, Trace
+pub const LOG_LEVEL : log::LevelFilter = log::LevelFilter::Info;
+
+pub const MAX_UPDATES : u64 = 200;
+
+///////////////////////////////////////////////////////////////////////////////
+//  session                                                                  //
+///////////////////////////////////////////////////////////////////////////////
+
+apis::def_session! {
+  context CounterRender {
+    PROCESSES where
+      let process    = self,
+      let message_in = message_in
+    [
+      process Counter (update_count : u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (1),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
+        }
+        sourcepoints   [Snapshots]
+        endpoints      []
+        handle_message { unreachable!() }
+        update         { process.counter_update() }
+      }
+      process Render (rendered : u64) -> (u64) {
+        kind           { apis::process::Kind::asynchronous_default() }
+        sourcepoints   []
+        endpoints      [Snapshots]
+        handle_message { process.render_handle_message (message_in) }
+        update         { apis::process::ControlFlow::Continue }
+      }
+    ]
+    CHANNELS  [
+      channel Snapshots <Snapshotmessage> (Buffer) {
+        producers [Counter]
+        consumers [Render]
+      }
+    ]
+    MESSAGES [
+      message Snapshotmessage {
+        Count (u64),
+        Quit
+      }
+    ]
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls                                                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+impl Counter {
+  fn counter_update (&mut self) -> apis::process::ControlFlow {
+    use apis::Process;
+    self.update_count += 1;
+    if self.update_count == MAX_UPDATES {
+      let _ = self.send (ChannelId::Snapshots, Snapshotmessage::Quit);
+      apis::process::ControlFlow::Break
+    } else {
+      self.send (
+        ChannelId::Snapshots, Snapshotmessage::Count (self.update_count)
+      ).into()
+    }
+  }
+}
+// end impl Counter
+
+impl Render {
+  fn render_handle_message (&mut self, message : GlobalMessage)
+    -> apis::process::ControlFlow
+  {
+    match message {
+      GlobalMessage::Snapshotmessage (Snapshotmessage::Quit) => {
+        println!("render rendered {} of {} snapshots", self.rendered, MAX_UPDATES);
+        self.result = self.rendered;
+        apis::process::ControlFlow::Break
+      }
+      GlobalMessage::Snapshotmessage (Snapshotmessage::Count (count)) => {
+        // a slow consumer
+        std::thread::sleep (std::time::Duration::from_millis (10));
+        println!("render count: {count}");
+        self.rendered += 1;
+        apis::process::ControlFlow::Continue
+      }
+    }
+  }
+}
+// end impl Render
+
+///////////////////////////////////////////////////////////////////////////////
+//  main                                                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+fn main() {
+  use std::io::Write;
+  use colored::Colorize;
+  use apis::session::Context;
+
+  let example_name = std::path::PathBuf::from (std::env::args().next().unwrap())
+    .file_name().unwrap().to_str().unwrap().to_string();
+
+  println!("{}", format!("{example_name} main...").green().bold());
+
+  env_logger::Builder::new()
+    .filter_level (LOG_LEVEL)
+    .parse_default_env()
+    .init();
+
+  // here is where we find out if the session definition has any errors
+  let session_def = CounterRender::def().unwrap();
+  // create a dotfile for the session
+  let mut f = std::fs::File::create (format!("{example_name}.dot")).unwrap();
+  f.write_all (session_def.dotfile_show_defaults().as_bytes()).unwrap();
+  drop (f);
+  // create the session from the definition
+  let mut session : apis::Session <CounterRender> = session_def.into();
+  // run to completion
+  let results = session.run();
+  println!("results: {results:?}");
+
+  println!("{}", format!("...{example_name} main").green().bold());
+}
@@ -0,0 +1,163 @@
+//! Example of a session consisting of a high-rate control loop and a monitor
+//! connected by a 'Simplex' channel.
+//!
+//! The controller is an 'Isochronous' (timed, polling) process with a 500us
+//! tick length (2 kHz) using a `Hybrid` sleep strategy: it sleeps the thread
+//! until 100us before each tick and then busy-waits for the remainder. Ticks
+//! missed after a stall are skipped rather than processed back to back. On each
+//! update it sends the time since the previous update, taken from its
+//! `UpdateContext`, to the monitor, an 'Asynchronous' process that reports the
+//! minimum, mean, and maximum update periods when the controller finishes.
+//!
+//! Running this example will produce a DOT file representing the data flow
+//! diagram of the session. To create a PNG image from the generated DOT file:
+//!
+//! ```bash
+//! make -f MakefileDot control
+//! ```
+
+#![allow(dead_code)]
+
+use colored;
+use env_logger;
+use log;
+
+use apis;
+
+///////////////////////////////////////////////////////////////////////////////
+//  constants                                                                //
+///////////////////////////////////////////////////////////////////////////////
+
+//  Off, Error, Warn, Info, Debug, Trace
+pub const LOG_LEVEL : log::LevelFilter = log::LevelFilter::Error;
+
+pub const MAX_UPDATES : u64 = 1000;
+
+///////////////////////////////////////////////////////////////////////////////
+//  session                                                                  //
+///////////////////////////////////////////////////////////////////////////////
+
+apis::def_session! {
+  context ControlLoop {
+    PROCESSES where
+      let process    = self,
+      let message_in = message_in
+    [
+      process Controller (updates : u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_micros (500),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Hybrid {
+              spin: std::time::Duration::from_micros (100)
+            },
+            catch_up:         apis::process::CatchUp::Skip
+          }
+        }
+        sourcepoints   [Periods]
+        endpoints      []
+        handle_message { unreachable!() }
+        update         { process.controller_update() }
+      }
+      process Monitor (periods : Vec <u64>) {
+        kind           { apis::process::Kind::asynchronous_default() }
+        sourcepoints   []
+        endpoints      [Periods]
+        terminate      { process.monitor_report() }
+        handle_message { process.monitor_handle_message (message_in) }
+        update         { apis::process::ControlFlow::Continue }
+      }
+    ]
+    CHANNELS  [
+      channel Periods <Periodsmessage> (Simplex) {
+        producers [Controller]
+        consumers [Monitor]
+      }
+    ]
+    MESSAGES [
+      message Periodsmessage {
+        PeriodNs (u64)
+      }
+    ]
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls                                                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+impl Controller {
+  fn controller_update (&mut self) -> apis::process::ControlFlow {
+    use apis::Process;
+    let update_context = *self.update_context();
+    let result = if 0 < update_context.update {
+      let period_ns = u64::try_from (update_context.dt.as_nanos()).unwrap();
+      self.send_typed (channels::Periods, Periodsmessage::PeriodNs (period_ns))
+    } else {
+      Ok (())
+    };
+    self.updates += 1;
+    if result.is_err() || MAX_UPDATES <= self.updates {
+      apis::process::ControlFlow::Break
+    } else {
+      apis::process::ControlFlow::Continue
+    }
+  }
+}
+
+impl Monitor {
+  fn monitor_handle_message (&mut self, message : GlobalMessage)
+    -> apis::process::ControlFlow
+  {
+    let GlobalMessage::Periodsmessage (Periodsmessage::PeriodNs (period_ns))
+      = message;
+    self.periods.push (period_ns);
+    apis::process::ControlFlow::Continue
+  }
+
+  fn monitor_report (&self) {
+    let count = self.periods.len() as u64;
+    if count == 0 {
+      return
+    }
+    let min  = self.periods.iter().min().unwrap();
+    let max  = self.periods.iter().max().unwrap();
+    let mean = self.periods.iter().sum::<u64>() / count;
+    println!("monitor: {count} update periods (ns): \
+      min {min} mean {mean} max {max}");
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  main                                                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+fn main() {
+  use std::io::Write;
+  use colored::Colorize;
+  use apis::session::Context;
+
+  let example_name = std::path::PathBuf::from (std::env::args().next().unwrap())
+    .file_name().unwrap().to_str().unwrap().to_string();
+
+  println!("{}", format!("{example_name} main...").green().bold());
+
+  env_logger::Builder::new()
+    .filter_level (LOG_LEVEL)
+    .parse_default_env()
+    .init();
+
+  // here is where we find out if the session definition has any errors
+  let session_def = ControlLoop::def().unwrap();
+  // create a dotfile for the session
+  let mut f = std::fs::File::create (format!("{example_name}.dot")).unwrap();
+  f.write_all (session_def.dotfile_show_defaults().as_bytes()).unwrap();
+  drop (f);
+  // create the session from the definition
+  let mut session : apis::Session <ControlLoop> = session_def.into();
+  // run to completion
+  let results = session.run();
+  println!("results: {results:?}");
+
+  println!("{}", format!("...{example_name} main").green().bold());
+}
@@ -40,7 +40,12 @@ apis::def_session! {
     [
       process Foosource () {
         kind {
-          apis::process::Kind::Isochronous { tick_ms: 20, ticks_per_update: 1 }
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (20),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
         }
         sourcepoints   [Foochan]
         endpoints      []
@@ -61,14 +66,14 @@ apis::def_session! {
         }
       }
       process Hangup1 () {
-        kind           { apis::process::Kind::Anisochronous }
+        kind           { apis::process::Kind::anisochronous_default() }
         sourcepoints   []
         endpoints      [Foochan]
         handle_message { unreachable!() }
         update         { apis::process::ControlFlow::Break }
       }
       process Hangup2 () {
-        kind           { apis::process::Kind::Anisochronous }
+        kind           { apis::process::Kind::anisochronous_default() }
         sourcepoints   []
         endpoints      [Foochan]
         handle_message { unreachable!() }
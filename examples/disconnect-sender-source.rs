@@ -40,7 +40,12 @@ apis::def_session! {
     [
       process Hangup () {
         kind {
-          apis::process::Kind::Isochronous { tick_ms: 20, ticks_per_update: 1 }
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (20),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
         }
         sourcepoints   [Foochan]
         endpoints      []
@@ -157,7 +157,7 @@ pub mod bgr {
             }
           }
         ) -> (ModeControl) {
-          kind           { apis::process::Kind::Anisochronous }
+          kind           { apis::process::Kind::anisochronous_default() }
           sourcepoints   [ ]
           endpoints      [ ]
           initialize     { println!("...BGR initialize..."); }
@@ -258,7 +258,7 @@ pub mod cym {
           clear_color          : (f32, f32, f32, f32) = (0.0, 1.0, 1.0, 1.0),
           glutin_glium_context : Option <GlutinGliumContext> = None
         ) -> (ModeControl) {
-          kind           { apis::process::Kind::Anisochronous }
+          kind           { apis::process::Kind::anisochronous_default() }
           sourcepoints   []
           endpoints      []
           terminate      { println!("...CYM terminate..."); }
@@ -358,7 +358,7 @@ pub mod wsk {
           clear_color          : (f32, f32, f32, f32) = (1.0, 1.0, 1.0, 1.0),
           glutin_glium_context : Option <GlutinGliumContext> = None
         ) -> (ModeControl) {
-          kind           { apis::process::Kind::Anisochronous }
+          kind           { apis::process::Kind::anisochronous_default() }
           sourcepoints   []
           endpoints      []
           initialize     { println!("...wsk initialize..."); }
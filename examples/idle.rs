@@ -0,0 +1,158 @@
+//! Example of a session consisting of one sender and two 'Anisochronous'
+//! receivers connected by a 'Broadcast' channel.
+//!
+//! The producer is an 'Isochronous' process sending a count every 10ms. The
+//! first consumer parks when idle and is woken only when a message is sent or
+//! the producer disconnects, so it only updates around each message. The
+//! second consumer sleeps with exponential backoff when idle, so it updates
+//! more often but still leaves the core idle between messages. Each consumer
+//! reports its number of updates in the session result.
+//!
+//! Running this example will produce a DOT file representing the data flow
+//! diagram of the session. To create a PNG image from the generated DOT file:
+//!
+//! ```bash
+//! make -f MakefileDot idle
+//! ```
+
+#![allow(dead_code)]
+
+use colored;
+use env_logger;
+use log;
+
+use apis;
+
+///////////////////////////////////////////////////////////////////////////////
+//  constants                                                                //
+///////////////////////////////////////////////////////////////////////////////
+
+//  Off, Error, Warn, Info, Debug, Trace
+pub const LOG_LEVEL : log::LevelFilter = log::LevelFilter::Info;
+
+pub const MAX_COUNT : u64 = 20;
+
+///////////////////////////////////////////////////////////////////////////////
+//  session                                                                  //
+///////////////////////////////////////////////////////////////////////////////
+
+apis::def_session! {
+  context CountIdle {
+    PROCESSES where
+      let process    = self,
+      let message_in = message_in
+    [
+      process Counter (count : u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (10),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
+        }
+        sourcepoints   [Counts]
+        endpoints      []
+        handle_message { unreachable!() }
+        update         { process.counter_update() }
+      }
+      process Parker (updates : u64) -> (u64) {
+        kind {
+          apis::process::Kind::Anisochronous {
+            idle: apis::process::Idle::Park
+          }
+        }
+        sourcepoints   []
+        endpoints      [Counts]
+        terminate      { process.result = process.updates; }
+        handle_message { apis::process::ControlFlow::Continue }
+        update {
+          process.updates += 1;
+          apis::process::ControlFlow::Continue
+        }
+      }
+      process Backer (updates : u64) -> (u64) {
+        kind {
+          apis::process::Kind::Anisochronous {
+            idle: apis::process::Idle::Backoff {
+              min: std::time::Duration::from_micros (100),
+              max: std::time::Duration::from_millis (4)
+            }
+          }
+        }
+        sourcepoints   []
+        endpoints      [Counts]
+        terminate      { process.result = process.updates; }
+        handle_message { apis::process::ControlFlow::Continue }
+        update {
+          process.updates += 1;
+          apis::process::ControlFlow::Continue
+        }
+      }
+    ]
+    CHANNELS  [
+      channel Counts <Countsmessage> (Broadcast) {
+        producers [Counter]
+        consumers [Parker, Backer]
+      }
+    ]
+    MESSAGES [
+      #[derive(Clone)]
+      message Countsmessage {
+        Count (u64)
+      }
+    ]
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls                                                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+impl Counter {
+  fn counter_update (&mut self) -> apis::process::ControlFlow {
+    use apis::Process;
+    self.count += 1;
+    let result = self.send_typed (channels::Counts,
+      Countsmessage::Count (self.count));
+    if result.is_err() || MAX_COUNT <= self.count {
+      apis::process::ControlFlow::Break
+    } else {
+      apis::process::ControlFlow::Continue
+    }
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  main                                                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+fn main() {
+  use std::io::Write;
+  use colored::Colorize;
+  use apis::session::Context;
+
+  let example_name = std::path::PathBuf::from (std::env::args().next().unwrap())
+    .file_name().unwrap().to_str().unwrap().to_string();
+
+  println!("{}", format!("{example_name} main...").green().bold());
+
+  env_logger::Builder::new()
+    .filter_level (LOG_LEVEL)
+    .parse_default_env()
+    .init();
+
+  // here is where we find out if the session definition has any errors
+  let session_def = CountIdle::def().unwrap();
+  // create a dotfile for the session
+  let mut f = std::fs::File::create (format!("{example_name}.dot")).unwrap();
+  f.write_all (session_def.dotfile_show_defaults().as_bytes()).unwrap();
+  drop (f);
+  // create the session from the definition
+  let mut session : apis::Session <CountIdle> = session_def.into();
+  // run to completion
+  let results = session.run();
+  println!("results: {results:?}");
+
+  println!("{}", format!("...{example_name} main").green().bold());
+}
@@ -108,7 +108,7 @@ pub mod readline_echoup {
         process Readline (
           dropthing : Option <Dropthing> = Some (Default::default())
         ) -> (Option <()>) {
-          kind           { apis::process::Kind::Anisochronous }
+          kind           { apis::process::Kind::anisochronous_default() }
           sourcepoints   [Toecho]
           endpoints      [Fromecho]
           handle_message { process.readline_handle_message (message_in) }
@@ -268,7 +268,7 @@ pub mod readline_echorev {
         process Readline (
           dropthing : Option <Dropthing> = None
         ) -> (Option <()>) {
-          kind           { apis::process::Kind::Anisochronous }
+          kind           { apis::process::Kind::anisochronous_default() }
           sourcepoints   [Toecho]
           endpoints      [Fromecho]
           handle_message { process.readline_handle_message (message_in) }
@@ -0,0 +1,204 @@
+//! Example of a session consisting of two senders and one receiver connected by
+//! two 'Simplex' channels, one carrying occasional control messages and the
+//! other a flood of bulk data.
+//!
+//! The receiver is an 'Isochronous' (timed, polling) process with a `Weighted`
+//! polling policy: on each tick it receives up to one command and then up to
+//! eight samples per round until both channels are empty, so a command never
+//! waits behind more than one round of samples. Each command reports how many
+//! samples were received earlier in the same tick. The receiver exits once
+//! both senders have finished and disconnected.
+//!
+//! Running this example will produce a DOT file representing the data flow
+//! diagram of the session. To create a PNG image from the generated DOT file:
+//!
+//! ```bash
+//! make -f MakefileDot poll
+//! ```
+
+#![allow(dead_code)]
+
+use colored;
+use env_logger;
+use log;
+
+use apis;
+
+///////////////////////////////////////////////////////////////////////////////
+//  constants                                                                //
+///////////////////////////////////////////////////////////////////////////////
+
+//  Off, Error, Warn, Info, Debug, Trace
+pub const LOG_LEVEL : log::LevelFilter = log::LevelFilter::Error;
+
+pub const MAX_COMMANDS      : u64 = 10;
+pub const MAX_BURSTS         : u64 = 200;
+pub const SAMPLES_PER_BURST  : u64 = 50;
+
+///////////////////////////////////////////////////////////////////////////////
+//  session                                                                  //
+///////////////////////////////////////////////////////////////////////////////
+
+apis::def_session! {
+  context ControlFlood {
+    PROCESSES where
+      let process    = self,
+      let message_in = message_in
+    [
+      process Commander (count : u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (20),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
+        }
+        sourcepoints   [Control]
+        endpoints      []
+        handle_message { unreachable!() }
+        update         { process.commander_update() }
+      }
+      process Flooder (bursts : u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (1),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
+        }
+        sourcepoints   [Samples]
+        endpoints      []
+        handle_message { unreachable!() }
+        update         { process.flooder_update() }
+      }
+      process Receiver (tick_samples : u64, samples : u64) -> (u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (5),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
+        }
+        sourcepoints   []
+        endpoints      [Control, Samples]
+        poll           { apis::process::Poll::Weighted { weights: vec![1, 8] } }
+        terminate      { process.result = process.samples; }
+        handle_message { process.receiver_handle_message (message_in) }
+        update {
+          process.tick_samples = 0;
+          apis::process::ControlFlow::Continue
+        }
+      }
+    ]
+    CHANNELS  [
+      channel Samples <Samplesmessage> (Simplex) {
+        producers [Flooder]
+        consumers [Receiver]
+      }
+      channel Control <Controlmessage> (Simplex) {
+        producers [Commander]
+        consumers [Receiver]
+      }
+    ]
+    MESSAGES [
+      message Controlmessage {
+        Command (u64)
+      }
+      message Samplesmessage {
+        Sample (u64)
+      }
+    ]
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls                                                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+impl Commander {
+  fn commander_update (&mut self) -> apis::process::ControlFlow {
+    use apis::Process;
+    self.count += 1;
+    let result = self.send_typed (channels::Control,
+      Controlmessage::Command (self.count));
+    if result.is_err() || MAX_COMMANDS <= self.count {
+      apis::process::ControlFlow::Break
+    } else {
+      apis::process::ControlFlow::Continue
+    }
+  }
+}
+
+impl Flooder {
+  fn flooder_update (&mut self) -> apis::process::ControlFlow {
+    use apis::Process;
+    for sample in 0..SAMPLES_PER_BURST {
+      let result = self.send_typed (channels::Samples,
+        Samplesmessage::Sample (self.bursts * SAMPLES_PER_BURST + sample));
+      if result.is_err() {
+        return apis::process::ControlFlow::Break
+      }
+    }
+    self.bursts += 1;
+    if MAX_BURSTS <= self.bursts {
+      apis::process::ControlFlow::Break
+    } else {
+      apis::process::ControlFlow::Continue
+    }
+  }
+}
+
+impl Receiver {
+  fn receiver_handle_message (&mut self, message : GlobalMessage)
+    -> apis::process::ControlFlow
+  {
+    match message {
+      GlobalMessage::Controlmessage (Controlmessage::Command (command)) => {
+        println!("receiver: command {command} after {} samples this tick",
+          self.tick_samples);
+      }
+      GlobalMessage::Samplesmessage (Samplesmessage::Sample (_)) => {
+        self.tick_samples += 1;
+        self.samples      += 1;
+      }
+    }
+    apis::process::ControlFlow::Continue
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  main                                                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+fn main() {
+  use std::io::Write;
+  use colored::Colorize;
+  use apis::session::Context;
+
+  let example_name = std::path::PathBuf::from (std::env::args().next().unwrap())
+    .file_name().unwrap().to_str().unwrap().to_string();
+
+  println!("{}", format!("{example_name} main...").green().bold());
+
+  env_logger::Builder::new()
+    .filter_level (LOG_LEVEL)
+    .parse_default_env()
+    .init();
+
+  // here is where we find out if the session definition has any errors
+  let session_def = ControlFlood::def().unwrap();
+  // create a dotfile for the session
+  let mut f = std::fs::File::create (format!("{example_name}.dot")).unwrap();
+  f.write_all (session_def.dotfile_show_defaults().as_bytes()).unwrap();
+  drop (f);
+  // create the session from the definition
+  let mut session : apis::Session <ControlFlood> = session_def.into();
+  // run to completion
+  let results = session.run();
+  println!("results: {results:?}");
+
+  println!("{}", format!("...{example_name} main").green().bold());
+}
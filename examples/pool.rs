@@ -0,0 +1,184 @@
+//! Example of a session consisting of two dispatchers and three workers
+//! connected by a 'Pool' channel.
+//!
+//! The dispatchers are 'Isochronous' (timed, polling) processes with a 5ms
+//! tick length that each send a job on every update. The workers are
+//! 'Asynchronous' processes that take a varying amount of time to complete
+//! each job. Each job is received by exactly one worker: whichever worker is
+//! idle first. When both dispatchers have finished and disconnected, the
+//! workers drain the remaining jobs and exit.
+//!
+//! Running this example will produce a DOT file representing the data flow
+//! diagram of the session. To create a PNG image from the generated DOT file:
+//!
+//! ```bash
+//! make -f MakefileDot pool
+//! ```
+
+#![allow(dead_code)]
+
+use colored;
+use env_logger;
+use log;
+
+use apis;
+
+///////////////////////////////////////////////////////////////////////////////
+//  constants                                                                //
+///////////////////////////////////////////////////////////////////////////////
+
+//  Off, Error, Warn, Info, Debug, Trace
+pub const LOG_LEVEL : log::LevelFilter = log::LevelFilter::Info;
+
+pub const JOBS_PER_DISPATCHER : u64 = 20;
+
+///////////////////////////////////////////////////////////////////////////////
+//  session                                                                  //
+///////////////////////////////////////////////////////////////////////////////
+
+apis::def_session! {
+  context DispatchWork {
+    PROCESSES where
+      let process    = self,
+      let message_in = message_in
+    [
+      process Dispatcher1 (jobs : u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (5),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
+        }
+        sourcepoints   [Jobs]
+        endpoints      []
+        handle_message { unreachable!() }
+        update         { process.dispatch (1000) }
+      }
+      process Dispatcher2 (jobs : u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (5),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
+        }
+        sourcepoints   [Jobs]
+        endpoints      []
+        handle_message { unreachable!() }
+        update         { process.dispatch (2000) }
+      }
+      process Worker1 (done : u64) -> (u64) {
+        kind           { apis::process::Kind::asynchronous_default() }
+        sourcepoints   []
+        endpoints      [Jobs]
+        terminate      { process.result = process.done; }
+        handle_message { work (1, &mut process.done, message_in) }
+        update         { apis::process::ControlFlow::Continue }
+      }
+      process Worker2 (done : u64) -> (u64) {
+        kind           { apis::process::Kind::asynchronous_default() }
+        sourcepoints   []
+        endpoints      [Jobs]
+        terminate      { process.result = process.done; }
+        handle_message { work (2, &mut process.done, message_in) }
+        update         { apis::process::ControlFlow::Continue }
+      }
+      process Worker3 (done : u64) -> (u64) {
+        kind           { apis::process::Kind::asynchronous_default() }
+        sourcepoints   []
+        endpoints      [Jobs]
+        terminate      { process.result = process.done; }
+        handle_message { work (3, &mut process.done, message_in) }
+        update         { apis::process::ControlFlow::Continue }
+      }
+    ]
+    CHANNELS  [
+      channel Jobs <Jobsmessage> (Pool) {
+        producers [Dispatcher1, Dispatcher2]
+        consumers [Worker1, Worker2, Worker3]
+      }
+    ]
+    MESSAGES [
+      message Jobsmessage {
+        Job (u64)
+      }
+    ]
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls                                                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+macro_rules! impl_dispatcher {
+  ($process:ident) => {
+    impl $process {
+      fn dispatch (&mut self, base : u64) -> apis::process::ControlFlow {
+        use apis::Process;
+        self.jobs += 1;
+        let result =
+          self.send (ChannelId::Jobs, Jobsmessage::Job (base + self.jobs));
+        if result.is_err() || JOBS_PER_DISPATCHER <= self.jobs {
+          apis::process::ControlFlow::Break
+        } else {
+          apis::process::ControlFlow::Continue
+        }
+      }
+    }
+  }
+}
+
+impl_dispatcher!(Dispatcher1);
+impl_dispatcher!(Dispatcher2);
+
+///////////////////////////////////////////////////////////////////////////////
+//  functions                                                                //
+///////////////////////////////////////////////////////////////////////////////
+
+fn work (worker : u64, done : &mut u64, message : GlobalMessage)
+  -> apis::process::ControlFlow
+{
+  let GlobalMessage::Jobsmessage (Jobsmessage::Job (job)) = message;
+  // workers take different amounts of time for each job
+  std::thread::sleep (std::time::Duration::from_millis (worker * 4));
+  println!("worker {worker} finished job {job}");
+  *done += 1;
+  apis::process::ControlFlow::Continue
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  main                                                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+fn main() {
+  use std::io::Write;
+  use colored::Colorize;
+  use apis::session::Context;
+
+  let example_name = std::path::PathBuf::from (std::env::args().next().unwrap())
+    .file_name().unwrap().to_str().unwrap().to_string();
+
+  println!("{}", format!("{example_name} main...").green().bold());
+
+  env_logger::Builder::new()
+    .filter_level (LOG_LEVEL)
+    .parse_default_env()
+    .init();
+
+  // here is where we find out if the session definition has any errors
+  let session_def = DispatchWork::def().unwrap();
+  // create a dotfile for the session
+  let mut f = std::fs::File::create (format!("{example_name}.dot")).unwrap();
+  f.write_all (session_def.dotfile_show_defaults().as_bytes()).unwrap();
+  drop (f);
+  // create the session from the definition
+  let mut session : apis::Session <DispatchWork> = session_def.into();
+  // run to completion
+  let results = session.run();
+  println!("results: {results:?}");
+
+  println!("{}", format!("...{example_name} main").green().bold());
+}
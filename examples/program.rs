@@ -95,7 +95,12 @@ pub mod chargen_upcase {
         //
         process Chargen (update_count : u64) {
           kind {
-            apis::process::Kind::Isochronous { tick_ms: 20, ticks_per_update: 1 }
+            apis::process::Kind::Isochronous {
+              tick:             std::time::Duration::from_millis (20),
+              ticks_per_update: 1,
+              sleep:            apis::process::Sleep::Thread,
+              catch_up:         apis::process::CatchUp::All
+            }
           }
           sourcepoints   [Charstream]
           endpoints      []
@@ -209,7 +214,12 @@ pub mod rand_source {
           dropthing    : Option <Dropthing> = None
         ) {
           kind {
-            apis::process::Kind::Isochronous { tick_ms: 20, ticks_per_update: 1 }
+            apis::process::Kind::Isochronous {
+              tick:             std::time::Duration::from_millis (20),
+              ticks_per_update: 1,
+              sleep:            apis::process::Sleep::Thread,
+              catch_up:         apis::process::CatchUp::All
+            }
           }
           sourcepoints   [Randints]
           endpoints      []
@@ -35,7 +35,14 @@ pub mod int_source {
         let message_in = message_in
       [
         process IntGen (update_count : u64) {
-          kind { apis::process::Kind::Isochronous { tick_ms: 20, ticks_per_update: 1 } }
+          kind {
+            apis::process::Kind::Isochronous {
+              tick:             std::time::Duration::from_millis (20),
+              ticks_per_update: 1,
+              sleep:            apis::process::Sleep::Thread,
+              catch_up:         apis::process::CatchUp::All
+            }
+          }
           sourcepoints   [Ints]
           endpoints      []
           handle_message { unreachable!() }
@@ -139,7 +146,12 @@ pub mod char_sink {
       [
         process Chargen1 (update_count : u64) {
           kind {
-            apis::process::Kind::Isochronous { tick_ms: 20, ticks_per_update: 1 }
+            apis::process::Kind::Isochronous {
+              tick:             std::time::Duration::from_millis (20),
+              ticks_per_update: 1,
+              sleep:            apis::process::Sleep::Thread,
+              catch_up:         apis::process::CatchUp::All
+            }
           }
           sourcepoints   [Charstream]
           endpoints      []
@@ -166,7 +178,12 @@ pub mod char_sink {
 
         process Chargen2 (update_count : u64) {
           kind {
-            apis::process::Kind::Isochronous { tick_ms: 20, ticks_per_update: 1 }
+            apis::process::Kind::Isochronous {
+              tick:             std::time::Duration::from_millis (20),
+              ticks_per_update: 1,
+              sleep:            apis::process::Sleep::Thread,
+              catch_up:         apis::process::CatchUp::All
+            }
           }
           sourcepoints   [Charstream]
           endpoints      []
@@ -0,0 +1,188 @@
+//! Example of a session consisting of two senders and one receiver connected by
+//! two 'Simplex' channels.
+//!
+//! The producers are 'Isochronous' (timed, polling) processes with different
+//! tick lengths: the first sends a count every 5ms and the second sends a word
+//! every 12ms. The consumer is an 'Asynchronous' process holding both
+//! endpoints; it blocks until a message arrives on either channel and handles
+//! each message as it arrives. The consumer exits once both producers have
+//! finished and disconnected.
+//!
+//! Running this example will produce a DOT file representing the data flow
+//! diagram of the session. To create a PNG image from the generated DOT file:
+//!
+//! ```bash
+//! make -f MakefileDot select
+//! ```
+
+#![allow(dead_code)]
+
+use colored;
+use env_logger;
+use log;
+
+use apis;
+
+///////////////////////////////////////////////////////////////////////////////
+//  constants                                                                //
+///////////////////////////////////////////////////////////////////////////////
+
+//  Off, Error, Warn, Info, Debug, Trace
+pub const LOG_LEVEL : log::LevelFilter = log::LevelFilter::Info;
+
+pub const MAX_COUNT : u64 = 20;
+
+pub const WORDS : [&str; 6] =
+  ["alpha", "beta", "gamma", "delta", "epsilon", "zeta"];
+
+///////////////////////////////////////////////////////////////////////////////
+//  session                                                                  //
+///////////////////////////////////////////////////////////////////////////////
+
+apis::def_session! {
+  context CountWordsMonitor {
+    PROCESSES where
+      let process    = self,
+      let message_in = message_in
+    [
+      process Counter (count : u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (5),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
+        }
+        sourcepoints   [Counts]
+        endpoints      []
+        handle_message { unreachable!() }
+        update         { process.counter_update() }
+      }
+      process Talker (index : usize) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (12),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
+        }
+        sourcepoints   [Words]
+        endpoints      []
+        handle_message { unreachable!() }
+        update         { process.talker_update() }
+      }
+      process Monitor (counts : u64, words : u64) -> (u64) {
+        kind           { apis::process::Kind::asynchronous_default() }
+        sourcepoints   []
+        endpoints      [Counts, Words]
+        terminate      { process.result = process.counts + process.words; }
+        handle_message { process.monitor_handle_message (message_in) }
+        update         { apis::process::ControlFlow::Continue }
+      }
+    ]
+    CHANNELS  [
+      channel Counts <Countsmessage> (Simplex) {
+        producers [Counter]
+        consumers [Monitor]
+      }
+      channel Words <Wordsmessage> (Simplex) {
+        producers [Talker]
+        consumers [Monitor]
+      }
+    ]
+    MESSAGES [
+      message Countsmessage {
+        Count (u64)
+      }
+      message Wordsmessage {
+        Word (String)
+      }
+    ]
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls                                                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+impl Counter {
+  fn counter_update (&mut self) -> apis::process::ControlFlow {
+    use apis::Process;
+    self.count += 1;
+    let result = self.send_typed (channels::Counts,
+      Countsmessage::Count (self.count));
+    if result.is_err() || MAX_COUNT <= self.count {
+      apis::process::ControlFlow::Break
+    } else {
+      apis::process::ControlFlow::Continue
+    }
+  }
+}
+
+impl Talker {
+  fn talker_update (&mut self) -> apis::process::ControlFlow {
+    use apis::Process;
+    let word = WORDS[self.index].to_string();
+    self.index += 1;
+    let result = self.send_typed (channels::Words, Wordsmessage::Word (word));
+    if result.is_err() || WORDS.len() <= self.index {
+      apis::process::ControlFlow::Break
+    } else {
+      apis::process::ControlFlow::Continue
+    }
+  }
+}
+
+impl Monitor {
+  fn monitor_handle_message (&mut self, message : GlobalMessage)
+    -> apis::process::ControlFlow
+  {
+    match message {
+      GlobalMessage::Countsmessage (Countsmessage::Count (count)) => {
+        println!("monitor: count {count}");
+        self.counts += 1;
+      }
+      GlobalMessage::Wordsmessage (Wordsmessage::Word (word)) => {
+        println!("monitor: word {word:?}");
+        self.words += 1;
+      }
+    }
+    apis::process::ControlFlow::Continue
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  main                                                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+fn main() {
+  use std::io::Write;
+  use colored::Colorize;
+  use apis::session::Context;
+
+  let example_name = std::path::PathBuf::from (std::env::args().next().unwrap())
+    .file_name().unwrap().to_str().unwrap().to_string();
+
+  println!("{}", format!("{example_name} main...").green().bold());
+
+  env_logger::Builder::new()
+    .filter_level (LOG_LEVEL)
+    .parse_default_env()
+    .init();
+
+  // here is where we find out if the session definition has any errors
+  let session_def = CountWordsMonitor::def().unwrap();
+  // create a dotfile for the session
+  let mut f = std::fs::File::create (format!("{example_name}.dot")).unwrap();
+  f.write_all (session_def.dotfile_show_defaults().as_bytes()).unwrap();
+  drop (f);
+  // create the session from the definition
+  let mut session : apis::Session <CountWordsMonitor> = session_def.into();
+  // run to completion
+  let results = session.run();
+  println!("results: {results:?}");
+
+  println!("{}", format!("...{example_name} main").green().bold());
+}
@@ -0,0 +1,190 @@
+//! Example of a session consisting of a client and a server connected by a
+//! bidirectional 'Session' channel.
+//!
+//! The channel protocol is declared as `Request | Quit ; Reply ; Repeat`: the
+//! client (producer) sends either a request or a quit message, the server
+//! (consumer) sends a reply, and the protocol starts over. The client is an
+//! 'Isochronous' (timed, polling) process that sends a request whenever it is
+//! not awaiting a reply. On its first update it also tries to send a second
+//! request before the reply has been received, which is rejected with a
+//! `SendError::Protocol` error. The server is an 'Asynchronous' process that
+//! replies to each request with the square of the requested number.
+//!
+//! Running this example will produce a DOT file representing the data flow
+//! diagram of the session. To create a PNG image from the generated DOT file:
+//!
+//! ```bash
+//! make -f MakefileDot session-typed
+//! ```
+
+#![allow(dead_code)]
+
+use colored;
+use env_logger;
+use log;
+
+use apis;
+
+///////////////////////////////////////////////////////////////////////////////
+//  constants                                                                //
+///////////////////////////////////////////////////////////////////////////////
+
+//  Off, Error, Warn, Info, Debug, Trace
+pub const LOG_LEVEL : log::LevelFilter = log::LevelFilter::Info;
+
+pub const MAX_REQUESTS : u64 = 10;
+
+///////////////////////////////////////////////////////////////////////////////
+//  session                                                                  //
+///////////////////////////////////////////////////////////////////////////////
+
+apis::def_session! {
+  context ClientServer {
+    PROCESSES where
+      let process    = self,
+      let message_in = message_in
+    [
+      process Client (
+        requests  : u64,
+        awaiting  : bool,
+        quit_sent : bool
+      ) -> (u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (10),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
+        }
+        sourcepoints   [Rpc]
+        endpoints      [Rpc]
+        handle_message { process.client_handle_message (message_in) }
+        update         { process.client_update() }
+      }
+      process Server () {
+        kind           { apis::process::Kind::asynchronous_default() }
+        sourcepoints   [Rpc]
+        endpoints      [Rpc]
+        handle_message { process.server_handle_message (message_in) }
+        update         { apis::process::ControlFlow::Continue }
+      }
+    ]
+    CHANNELS  [
+      channel Rpc <Rpcmessage> (Session) {
+        producers [Client]
+        consumers [Server]
+        protocol  { Request | Quit ; Reply ; Repeat }
+      }
+    ]
+    MESSAGES [
+      message Rpcmessage {
+        Request (u64),
+        Reply (u64),
+        Quit
+      }
+    ]
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls                                                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+impl Client {
+  fn client_update (&mut self) -> apis::process::ControlFlow {
+    use apis::{channel, Process};
+    if self.awaiting {
+      return apis::process::ControlFlow::Continue
+    }
+    if self.quit_sent {
+      println!("client sum of replies: {}", self.result);
+      return apis::process::ControlFlow::Break
+    }
+    if self.requests < MAX_REQUESTS {
+      self.requests += 1;
+      let result = self.send (ChannelId::Rpc, Rpcmessage::Request (self.requests));
+      if self.requests == 1 {
+        // the protocol requires a reply before the next request
+        match self.send (ChannelId::Rpc, Rpcmessage::Request (0)) {
+          Err (channel::SendError::Protocol (_)) =>
+            println!("client: second request rejected by protocol"),
+          _ => unreachable!()
+        }
+      }
+      self.awaiting = true;
+      result.into()
+    } else {
+      self.quit_sent = true;
+      self.awaiting  = true;
+      self.send (ChannelId::Rpc, Rpcmessage::Quit).into()
+    }
+  }
+
+  fn client_handle_message (&mut self, message : GlobalMessage)
+    -> apis::process::ControlFlow
+  {
+    match message {
+      GlobalMessage::Rpcmessage (Rpcmessage::Reply (reply)) => {
+        println!("client: reply {reply}");
+        self.result  += reply;
+        self.awaiting = false;
+        apis::process::ControlFlow::Continue
+      }
+      GlobalMessage::Rpcmessage (_) => unreachable!("server only sends replies")
+    }
+  }
+}
+// end impl Client
+
+impl Server {
+  fn server_handle_message (&self, message : GlobalMessage)
+    -> apis::process::ControlFlow
+  {
+    use apis::Process;
+    match message {
+      GlobalMessage::Rpcmessage (Rpcmessage::Request (n)) =>
+        self.send (ChannelId::Rpc, Rpcmessage::Reply (n * n)).into(),
+      GlobalMessage::Rpcmessage (Rpcmessage::Quit) => {
+        let _ = self.send (ChannelId::Rpc, Rpcmessage::Reply (0));
+        apis::process::ControlFlow::Break
+      }
+      GlobalMessage::Rpcmessage (_) => unreachable!("client only sends requests")
+    }
+  }
+}
+// end impl Server
+
+///////////////////////////////////////////////////////////////////////////////
+//  main                                                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+fn main() {
+  use std::io::Write;
+  use colored::Colorize;
+  use apis::session::Context;
+
+  let example_name = std::path::PathBuf::from (std::env::args().next().unwrap())
+    .file_name().unwrap().to_str().unwrap().to_string();
+
+  println!("{}", format!("{example_name} main...").green().bold());
+
+  env_logger::Builder::new()
+    .filter_level (LOG_LEVEL)
+    .parse_default_env()
+    .init();
+
+  // here is where we find out if the session definition has any errors
+  let session_def = ClientServer::def().unwrap();
+  // create a dotfile for the session
+  let mut f = std::fs::File::create (format!("{example_name}.dot")).unwrap();
+  f.write_all (session_def.dotfile_show_defaults().as_bytes()).unwrap();
+  drop (f);
+  // create the session from the definition
+  let mut session : apis::Session <ClientServer> = session_def.into();
+  // run to completion
+  let results = session.run();
+  println!("results: {results:?}");
+
+  println!("{}", format!("...{example_name} main").green().bold());
+}
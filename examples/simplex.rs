@@ -45,7 +45,12 @@ apis::def_session! {
     [
       process Chargen (update_count : u64) -> (Option <()>) {
         kind {
-          apis::process::Kind::Isochronous { tick_ms: 20, ticks_per_update: 1 }
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (20),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
         }
         sourcepoints   [Charstream]
         endpoints      []
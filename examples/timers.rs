@@ -0,0 +1,178 @@
+//! Example of a session consisting of one sender and one receiver connected by
+//! a 'Simplex' channel, both using process timers.
+//!
+//! The sender is an 'Isochronous' (timed, polling) process with a 20ms tick
+//! that sends a count on each tick except for a pause in the middle of the
+//! run. It also sets a 50ms interval timer on `initialize` to print the
+//! number of counts sent so far.
+//!
+//! The receiver is an 'Asynchronous' process that debounces its input: each
+//! message cancels the pending one-shot timer and sets a new one 60ms later,
+//! so the timer only expires, waking the blocked receiver, when the sender
+//! pauses. The receiver returns the number of pauses detected.
+//!
+//! Running this example will produce a DOT file representing the data flow
+//! diagram of the session. To create a PNG image from the generated DOT file:
+//!
+//! ```bash
+//! make -f MakefileDot timers
+//! ```
+
+#![allow(dead_code)]
+
+use colored;
+use env_logger;
+use log;
+
+use apis;
+
+///////////////////////////////////////////////////////////////////////////////
+//  constants                                                                //
+///////////////////////////////////////////////////////////////////////////////
+
+//  Off, Error, Warn, Info, Debug, Trace
+pub const LOG_LEVEL : log::LevelFilter = log::LevelFilter::Info;
+
+pub const MAX_TICKS : u64 = 20;
+/// Ticks on which nothing is sent
+pub const PAUSE     : std::ops::Range <u64> = 8..13;
+
+///////////////////////////////////////////////////////////////////////////////
+//  session                                                                  //
+///////////////////////////////////////////////////////////////////////////////
+
+apis::def_session! {
+  context Debounce {
+    PROCESSES where
+      let process    = self,
+      let message_in = message_in
+    [
+      process Sender (ticks : u64, sent : u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (20),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::All
+          }
+        }
+        sourcepoints   [Counts]
+        endpoints      []
+        initialize {
+          process.set_interval (std::time::Duration::from_millis (50))
+            .unwrap();
+        }
+        handle_timer (_timer) {
+          println!("sender: {} counts sent", process.sent);
+          apis::process::ControlFlow::Continue
+        }
+        handle_message { unreachable!() }
+        update         { process.sender_update() }
+      }
+      process Receiver (
+        quiet  : Option <apis::process::TimerId>,
+        pauses : u64
+      ) -> (u64) {
+        kind           { apis::process::Kind::asynchronous_default() }
+        sourcepoints   []
+        endpoints      [Counts]
+        terminate      { process.result = process.pauses; }
+        handle_timer (timer) { process.receiver_handle_timer (timer) }
+        handle_message { process.receiver_handle_message (message_in) }
+        update         { apis::process::ControlFlow::Continue }
+      }
+    ]
+    CHANNELS  [
+      channel Counts <Countsmessage> (Simplex) {
+        producers [Sender]
+        consumers [Receiver]
+      }
+    ]
+    MESSAGES [
+      message Countsmessage {
+        Count (u64)
+      }
+    ]
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls                                                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+impl Sender {
+  fn sender_update (&mut self) -> apis::process::ControlFlow {
+    use apis::Process;
+    let result = if PAUSE.contains (&self.ticks) {
+      Ok (())
+    } else {
+      self.sent += 1;
+      self.send_typed (channels::Counts, Countsmessage::Count (self.sent))
+    };
+    self.ticks += 1;
+    if result.is_err() || MAX_TICKS <= self.ticks {
+      apis::process::ControlFlow::Break
+    } else {
+      apis::process::ControlFlow::Continue
+    }
+  }
+}
+
+impl Receiver {
+  fn receiver_handle_message (&mut self, message : GlobalMessage)
+    -> apis::process::ControlFlow
+  {
+    use apis::Process;
+    let GlobalMessage::Countsmessage (Countsmessage::Count (count)) = message;
+    println!("receiver: count {count}");
+    if let Some (quiet) = self.quiet.take() {
+      self.cancel_timer (quiet);
+    }
+    self.quiet = Some (self.set_timer (std::time::Duration::from_millis (60)));
+    apis::process::ControlFlow::Continue
+  }
+
+  fn receiver_handle_timer (&mut self, timer : apis::process::TimerId)
+    -> apis::process::ControlFlow
+  {
+    debug_assert_eq!(self.quiet, Some (timer));
+    println!("receiver: input paused");
+    self.quiet   = None;
+    self.pauses += 1;
+    apis::process::ControlFlow::Continue
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  main                                                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+fn main() {
+  use std::io::Write;
+  use colored::Colorize;
+  use apis::session::Context;
+
+  let example_name = std::path::PathBuf::from (std::env::args().next().unwrap())
+    .file_name().unwrap().to_str().unwrap().to_string();
+
+  println!("{}", format!("{example_name} main...").green().bold());
+
+  env_logger::Builder::new()
+    .filter_level (LOG_LEVEL)
+    .parse_default_env()
+    .init();
+
+  // here is where we find out if the session definition has any errors
+  let session_def = Debounce::def().unwrap();
+  // create a dotfile for the session
+  let mut f = std::fs::File::create (format!("{example_name}.dot")).unwrap();
+  f.write_all (session_def.dotfile_show_defaults().as_bytes()).unwrap();
+  drop (f);
+  // create the session from the definition
+  let mut session : apis::Session <Debounce> = session_def.into();
+  // run to completion
+  let results = session.run();
+  println!("results: {results:?}");
+
+  println!("{}", format!("...{example_name} main").green().bold());
+}
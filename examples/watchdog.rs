@@ -0,0 +1,161 @@
+//! Example of a session consisting of a sender and a receiver connected by a
+//! 'Simplex' channel, where the receiver watches for missed heartbeats.
+//!
+//! The producer is an 'Isochronous' (timed, polling) process with a 10ms tick
+//! length that sends a heartbeat on every update. On the 10th update it
+//! stalls for 100ms; its `CatchUp::Report` policy skips the missed ticks and
+//! reports how many were skipped to the following update. The consumer is an 'Asynchronous' process with a 30ms
+//! idle timeout: each time the timeout elapses without a heartbeat its
+//! `handle_timeout` block reports the missed heartbeat.
+//!
+//! Running this example will produce a DOT file representing the data flow
+//! diagram of the session. To create a PNG image from the generated DOT file:
+//!
+//! ```bash
+//! make -f MakefileDot watchdog
+//! ```
+
+#![allow(dead_code)]
+
+use colored;
+use env_logger;
+use log;
+
+use apis;
+
+///////////////////////////////////////////////////////////////////////////////
+//  constants                                                                //
+///////////////////////////////////////////////////////////////////////////////
+
+//  Off, Error, Warn, Info, Debug, Trace
+pub const LOG_LEVEL : log::LevelFilter = log::LevelFilter::Info;
+
+pub const MAX_BEATS : u64 = 20;
+
+///////////////////////////////////////////////////////////////////////////////
+//  session                                                                  //
+///////////////////////////////////////////////////////////////////////////////
+
+apis::def_session! {
+  context HeartbeatWatchdog {
+    PROCESSES where
+      let process    = self,
+      let message_in = message_in
+    [
+      process Heart (beats : u64) {
+        kind {
+          apis::process::Kind::Isochronous {
+            tick:             std::time::Duration::from_millis (10),
+            ticks_per_update: 1,
+            sleep:            apis::process::Sleep::Thread,
+            catch_up:         apis::process::CatchUp::Report
+          }
+        }
+        sourcepoints   [Heartbeat]
+        endpoints      []
+        handle_message { unreachable!() }
+        update         { process.heart_update() }
+      }
+      process Watchdog (beats : u64) -> (u64) {
+        kind {
+          apis::process::Kind::Asynchronous {
+            messages_per_update: 1,
+            idle_timeout:        Some (std::time::Duration::from_millis (30))
+          }
+        }
+        sourcepoints   []
+        endpoints      [Heartbeat]
+        handle_timeout { process.watchdog_handle_timeout() }
+        handle_message { process.watchdog_handle_message (message_in) }
+        update         { apis::process::ControlFlow::Continue }
+      }
+    ]
+    CHANNELS  [
+      channel Heartbeat <Heartbeatmessage> (Simplex) {
+        producers [Heart]
+        consumers [Watchdog]
+      }
+    ]
+    MESSAGES [
+      message Heartbeatmessage {
+        Beat (u64)
+      }
+    ]
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls                                                                    //
+///////////////////////////////////////////////////////////////////////////////
+
+impl Heart {
+  fn heart_update (&mut self) -> apis::process::ControlFlow {
+    use apis::Process;
+    let missed_ticks = self.update_context().missed_ticks;
+    if 0 < missed_ticks {
+      println!("heart: skipped {missed_ticks} ticks");
+    }
+    self.beats += 1;
+    if self.beats == 10 {
+      std::thread::sleep (std::time::Duration::from_millis (100));
+    }
+    let result = self.send_typed (channels::Heartbeat,
+      Heartbeatmessage::Beat (self.beats));
+    if result.is_err() || MAX_BEATS <= self.beats {
+      apis::process::ControlFlow::Break
+    } else {
+      apis::process::ControlFlow::Continue
+    }
+  }
+}
+
+impl Watchdog {
+  fn watchdog_handle_timeout (&mut self) -> apis::process::ControlFlow {
+    println!("watchdog: missed heartbeat after beat {}", self.beats);
+    self.result += 1;
+    apis::process::ControlFlow::Continue
+  }
+
+  const fn watchdog_handle_message (&mut self, message : GlobalMessage)
+    -> apis::process::ControlFlow
+  {
+    let GlobalMessage::Heartbeatmessage (Heartbeatmessage::Beat (beat))
+      = message;
+    self.beats = beat;
+    apis::process::ControlFlow::Continue
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  main                                                                     //
+///////////////////////////////////////////////////////////////////////////////
+
+fn main() {
+  use std::io::Write;
+  use colored::Colorize;
+  use apis::session::Context;
+
+  let example_name = std::path::PathBuf::from (std::env::args().next().unwrap())
+    .file_name().unwrap().to_str().unwrap().to_string();
+
+  println!("{}", format!("{example_name} main...").green().bold());
+
+  env_logger::Builder::new()
+    .filter_level (LOG_LEVEL)
+    .parse_default_env()
+    .init();
+
+  // here is where we find out if the session definition has any errors
+  let session_def = HeartbeatWatchdog::def().unwrap();
+  // create a dotfile for the session
+  let mut f = std::fs::File::create (format!("{example_name}.dot")).unwrap();
+  f.write_all (session_def.dotfile_show_defaults().as_bytes()).unwrap();
+  drop (f);
+  // create the session from the definition
+  let mut session : apis::Session <HeartbeatWatchdog> = session_def.into();
+  // run to completion
+  let results = session.run();
+  println!("results: {results:?}");
+
+  println!("{}", format!("...{example_name} main").green().bold());
+}
@@ -0,0 +1,135 @@
+use {vec_map, unbounded_spsc};
+use crate::{channel, session, Message};
+use super::Backend;
+
+///////////////////////////////////////////////////////////////////////////////
+//  structs
+///////////////////////////////////////////////////////////////////////////////
+
+/// An SPMC broadcast; every message sent is cloned to each consumer.
+pub struct Broadcast <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX> + Clone
+{
+  def       : channel::Def <CTX>,
+  producer  : (CTX::PID, Sender <M>),
+  consumers : vec_map::VecMap <unbounded_spsc::Receiver <M>>
+}
+
+/// Broadcast sourcepoint holding one stream for each consumer.
+pub struct Sender <M> {
+  senders : vec_map::VecMap <unbounded_spsc::Sender <M>>
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls
+///////////////////////////////////////////////////////////////////////////////
+
+/// A broadcast `send` succeeds as long as at least one consumer is still
+/// connected; `send_to` delivers to a single consumer only.
+impl <CTX, M> channel::Sourcepoint <CTX> for Sender <M> where
+  CTX : session::Context,
+  M   : Message <CTX> + Clone
+{
+  fn send (&self, message : CTX::GMSG)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    let message = M::try_from (message).ok().unwrap();
+    let mut delivered = false;
+    for sender in self.senders.values() {
+      delivered |= sender.send (message.clone()).is_ok();
+    }
+    if delivered {
+      Ok (())
+    } else {
+      Err (channel::SendError::Disconnected (message.into()))
+    }
+  }
+  fn send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    let pid : usize = recipient.into();
+    let Some (sender) = self.senders.get (pid) else {
+      return Err (channel::SendError::WrongAddressing (message))
+    };
+    unbounded_spsc::Sender::send (sender, M::try_from (message).ok().unwrap())
+      .map_err (Into::into)
+  }
+  fn try_send (&self, message : CTX::GMSG)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    channel::Sourcepoint::<CTX>::send (self, message).map_err (Into::into)
+  }
+  fn try_send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    channel::Sourcepoint::<CTX>::send_to (self, message, recipient)
+      .map_err (Into::into)
+  }
+}
+
+impl <CTX, M> Backend <CTX> for Broadcast <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX> + Clone + 'static
+{}
+
+impl <CTX, M>
+  TryFrom <channel::Def <CTX>> for Broadcast <CTX, M>
+where
+  CTX : session::Context,
+  M   : Message <CTX> + Clone + 'static
+{
+  type Error = channel::CreateError;
+  fn try_from (def : channel::Def <CTX>) -> Result <Self, Self::Error> {
+    if def.capacity.is_some() {
+      return Err (channel::CreateError::CapacityMismatch)
+    }
+    match def.kind {
+      channel::Kind::Broadcast => {
+        let producer_id = def.producers[0].clone();
+        let mut senders = vec_map::VecMap::new();
+        let mut consumers = vec_map::VecMap::new();
+        for consumer_id in def.consumers.iter() {
+          let (sender, endpoint) = unbounded_spsc::channel();
+          assert!(
+            senders.insert (consumer_id.clone().into(), sender).is_none());
+          assert!(consumers.insert (consumer_id.clone().into(), endpoint)
+            .is_none());
+        }
+        Ok (Broadcast {
+          def,
+          producer: (producer_id, Sender { senders }),
+          consumers
+        })
+      },
+      _ => Err (channel::CreateError::KindMismatch)
+    }
+  }
+}
+
+impl <CTX, M> From <Broadcast <CTX, M>> for channel::Channel <CTX> where
+  CTX : session::Context,
+  M   : Message <CTX> + Clone + 'static
+{
+  fn from (broadcast : Broadcast <CTX, M>) -> Self {
+    let mut sourcepoints : vec_map::VecMap <Box <dyn channel::Sourcepoint <CTX>>>
+      = vec_map::VecMap::new();
+    let (producer_id, sourcepoint) = broadcast.producer;
+    assert!(
+      sourcepoints.insert (
+        producer_id.into(), Box::new (sourcepoint)
+      ).is_none());
+    let mut endpoints : vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>
+      = vec_map::VecMap::new();
+    for (consumer_id, endpoint) in broadcast.consumers.into_iter() {
+      assert!(endpoints.insert (consumer_id, Box::new (endpoint)).is_none());
+    }
+    channel::Channel {
+      def: broadcast.def,
+      sourcepoints,
+      endpoints,
+      injector: None,
+      observer: None
+    }
+  }
+}
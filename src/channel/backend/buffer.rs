@@ -1,87 +1,221 @@
-//use ::vec_map;
-
-//use ::triple_buffer;
+use std::sync::{Arc, Condvar, Mutex};
+use crate::{channel, session, Message};
+use super::Backend;
 
 // TODO: spmc_buffer
 
-//use ::channel;
-//use ::message;
-//use ::session;
+///////////////////////////////////////////////////////////////////////////////
+//  structs
+///////////////////////////////////////////////////////////////////////////////
 
-// TODO:
-/*
-/// An SPSC triple-buffer.
+/// An SPSC latest-value buffer.
+///
+/// Each `send` overwrites any value the consumer has not yet received, so
+/// the consumer only ever receives the most recent value. Values are moved
+/// out on receipt: `try_recv` returns `Empty` until a new value is sent.
 pub struct Buffer <CTX, M> where
   CTX : session::Context,
-  M   : Message
+  M   : Message <CTX>
 {
-  info     : channel::Info <CTX>,
-  producer : triple_buffer::TripleBufferInput  <M>,
-  consumer : triple_buffer::TripleBufferOutput <M>
+  def      : channel::Def <CTX>,
+  producer : (CTX::PID, Sender <M>),
+  consumer : (CTX::PID, Receiver <M>)
 }
 
-//
-//  impl Buffer
-//
+/// Buffer sourcepoint.
+pub struct Sender <M> {
+  shared : Arc <Shared <M>>
+}
 
-impl <CTX, M> Buffer <CTX, M> where
-  CTX : session::Context,
-  M   : Message
-{
-  pub fn new (info : channel::Info <CTX>) -> Self {
-    let triple_buffer = triple_buffer::TripleBuffer::new (Default::default());
-    let (producer, consumer) = triple_buffer.split();
-    Buffer {
-      info,
-      producer,
-      consumer
+/// Buffer endpoint.
+pub struct Receiver <M> {
+  shared : Arc <Shared <M>>
+}
+
+struct Shared <M> {
+  slot    : Mutex <Slot <M>>,
+  updated : Condvar
+}
+
+struct Slot <M> {
+  value        : Option <M>,
+  sender_alive : bool,
+  recver_alive : bool
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls
+///////////////////////////////////////////////////////////////////////////////
+
+impl <M> Sender <M> {
+  /// Overwrite the current value, returning the message if the receiver has
+  /// disconnected.
+  fn write (&self, message : M) -> Result <(), M> {
+    let mut slot = self.shared.slot.lock().unwrap();
+    if !slot.recver_alive {
+      return Err (message)
     }
+    slot.value = Some (message);
+    drop (slot);
+    self.shared.updated.notify_one();
+    Ok (())
   }
 }
 
-impl <CTX, M> channel::Channel <CTX, M> for Buffer <CTX, M> where
-  CTX : session::Context,
-  M   : Message + 'static
-{
-  fn info (&self) -> &channel::Info <CTX> {
-    &self.info
+impl <M> Drop for Sender <M> {
+  fn drop (&mut self) {
+    self.shared.slot.lock().unwrap().sender_alive = false;
+    self.shared.updated.notify_one();
   }
+}
 
-  fn decompose (self) -> (
-    vec_map::VecMap <Box <channel::Sourcepoint <CTX, M>>>,
-    vec_map::VecMap <Box <channel::Endpoint    <CTX, M>>>
-  ) {
-    unimplemented!()  // TODO
+impl <M> Drop for Receiver <M> {
+  fn drop (&mut self) {
+    self.shared.slot.lock().unwrap().recver_alive = false;
   }
 }
 
-impl <CTX, M>
-  channel::Sourcepoint <CTX, M> for triple_buffer::TripleBufferInput <M>
-where
+impl <CTX, M> channel::Sourcepoint <CTX> for Sender <M> where
   CTX : session::Context,
-  M   : Message
+  M   : Message <CTX>
 {
-  fn send (&mut self, message : M) {
-    self.write (message)
+  fn send (&self, message : CTX::GMSG)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    self.write (M::try_from (message).ok().unwrap())
+      .map_err (|message| channel::SendError::Disconnected (message.into()))
+  }
+  fn send_to (&self, message : CTX::GMSG, _recipient : CTX::PID)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    Err (channel::SendError::WrongAddressing (message))
+  }
+  fn try_send (&self, message : CTX::GMSG)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    channel::Sourcepoint::<CTX>::send (self, message).map_err (Into::into)
   }
+  fn try_send_to (&self, message : CTX::GMSG, _recipient : CTX::PID)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    Err (channel::TrySendError::WrongAddressing (message))
+  }
+}
 
-  fn send_to (&mut self, _message : M, _recipient : CTX::PID) {
-    unimplemented!()
+impl <CTX, M> channel::Endpoint <CTX> for Receiver <M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  fn recv (&self) -> Result <CTX::GMSG, channel::RecvError> {
+    let mut slot = self.shared.slot.lock().unwrap();
+    loop {
+      if let Some (message) = slot.value.take() {
+        return Ok (message.into())
+      }
+      if !slot.sender_alive {
+        return Err (channel::RecvError)
+      }
+      slot = self.shared.updated.wait (slot).unwrap();
+    }
+  }
+  fn try_recv (&self) -> Result <CTX::GMSG, channel::TryRecvError> {
+    let mut slot = self.shared.slot.lock().unwrap();
+    match slot.value.take() {
+      Some (message) => Ok (message.into()),
+      None if slot.sender_alive => Err (channel::TryRecvError::Empty),
+      None => Err (channel::TryRecvError::Disconnected)
+    }
+  }
+  fn recv_timeout (&self, timeout : std::time::Duration)
+    -> Result <CTX::GMSG, channel::RecvTimeoutError>
+  {
+    match std::time::Instant::now().checked_add (timeout) {
+      Some (deadline) => channel::Endpoint::<CTX>::recv_deadline (self, deadline),
+      None => channel::Endpoint::<CTX>::recv (self)
+        .map_err (|channel::RecvError| channel::RecvTimeoutError::Disconnected)
+    }
+  }
+  fn recv_deadline (&self, deadline : std::time::Instant)
+    -> Result <CTX::GMSG, channel::RecvTimeoutError>
+  {
+    let mut slot = self.shared.slot.lock().unwrap();
+    loop {
+      if let Some (message) = slot.value.take() {
+        return Ok (message.into())
+      }
+      if !slot.sender_alive {
+        return Err (channel::RecvTimeoutError::Disconnected)
+      }
+      let now = std::time::Instant::now();
+      if deadline <= now {
+        return Err (channel::RecvTimeoutError::Timeout)
+      }
+      slot = self.shared.updated.wait_timeout (slot, deadline - now).unwrap().0;
+    }
   }
 }
 
+impl <CTX, M> Backend <CTX> for Buffer <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{}
+
 impl <CTX, M>
-  channel::Endpoint <CTX, M> for triple_buffer::TripleBufferOutput <M>
+  TryFrom <channel::Def <CTX>> for Buffer <CTX, M>
 where
   CTX : session::Context,
-  M   : Message
+  M   : Message <CTX> + 'static
 {
-  fn recv (&mut self) -> M {
-    self.read().clone()
-  }
-  fn try_recv (&mut self) -> Option <M> {
-    Some (self.read().clone())
+  type Error = channel::CreateError;
+  fn try_from (def : channel::Def <CTX>) -> Result <Self, Self::Error> {
+    if def.capacity.is_some() {
+      return Err (channel::CreateError::CapacityMismatch)
+    }
+    match def.kind {
+      channel::Kind::Buffer => {
+        let producer_id = def.producers[0].clone();
+        let consumer_id = def.consumers[0].clone();
+        let shared = Arc::new (Shared {
+          slot:    Mutex::new (Slot {
+            value:        None,
+            sender_alive: true,
+            recver_alive: true
+          }),
+          updated: Condvar::new()
+        });
+        let sender   = Sender   { shared: shared.clone() };
+        let receiver = Receiver { shared };
+        Ok (Buffer {
+          def,
+          producer: (producer_id, sender),
+          consumer: (consumer_id, receiver)
+        })
+      },
+      _ => Err (channel::CreateError::KindMismatch)
+    }
   }
 }
-*/
 
+impl <CTX, M> From <Buffer <CTX, M>> for channel::Channel <CTX> where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{
+  fn from (buffer : Buffer <CTX, M>) -> Self {
+    let mut sourcepoints : vec_map::VecMap <Box <dyn channel::Sourcepoint <CTX>>>
+      = vec_map::VecMap::new();
+    let mut endpoints : vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>
+      = vec_map::VecMap::new();
+    let (producer_id, sender)   = buffer.producer;
+    let (consumer_id, receiver) = buffer.consumer;
+    assert!(
+      sourcepoints.insert (producer_id.into(), Box::new (sender)).is_none());
+    assert!(endpoints.insert (consumer_id.into(), Box::new (receiver)).is_none());
+    channel::Channel {
+      def: buffer.def,
+      sourcepoints,
+      endpoints,
+      injector: None,
+      observer: None
+    }
+  }
+}
@@ -7,8 +7,14 @@ use crate::{channel, session, Message};
 
 pub mod broadcast;
 pub mod buffer;
+pub mod pool;
 pub mod session_typed;
 
+pub use self::broadcast::Broadcast;
+pub use self::buffer::Buffer;
+pub use self::pool::Pool;
+pub use self::session_typed::Session;
+
 ///////////////////////////////////////////////////////////////////////////////
 //  structs
 ///////////////////////////////////////////////////////////////////////////////
@@ -20,7 +26,8 @@ pub struct Simplex <CTX, M> where
 {
   def      : channel::Def <CTX>,
   producer : (CTX::PID, unbounded_spsc::Sender <M>),
-  consumer : (CTX::PID, unbounded_spsc::Receiver <M>)
+  /// `None` for an external consumer
+  consumer : (Option <CTX::PID>, unbounded_spsc::Receiver <M>)
 }
 
 /// An MPSC sink.
@@ -30,7 +37,10 @@ pub struct Sink <CTX, M> where
 {
   def       : channel::Def <CTX>,
   producers : vec_map::VecMap <std::sync::mpsc::Sender <M>>,
-  consumer  : (CTX::PID, std::sync::mpsc::Receiver <M>)
+  /// `None` for an external consumer
+  consumer  : (Option <CTX::PID>, std::sync::mpsc::Receiver <M>),
+  /// Sourcepoint of an external producer
+  injector  : Option <std::sync::mpsc::Sender <M>>
 }
 
 /// An SPMC source.
@@ -43,6 +53,41 @@ pub struct Source <CTX, M> where
   consumers : vec_map::VecMap <unbounded_spsc::Receiver <M>>
 }
 
+/// A bounded SPSC stream.
+pub struct BoundedSimplex <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  def      : channel::Def <CTX>,
+  producer : (CTX::PID, std::sync::mpsc::SyncSender <M>),
+  /// `None` for an external consumer
+  consumer : (Option <CTX::PID>, std::sync::mpsc::Receiver <M>)
+}
+
+/// A bounded MPSC sink.
+pub struct BoundedSink <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  def       : channel::Def <CTX>,
+  producers : vec_map::VecMap <std::sync::mpsc::SyncSender <M>>,
+  /// `None` for an external consumer
+  consumer  : (Option <CTX::PID>, std::sync::mpsc::Receiver <M>),
+  /// Sourcepoint of an external producer
+  injector  : Option <std::sync::mpsc::SyncSender <M>>
+}
+
+/// A bounded SPMC source; each consumer has its own queue of the given
+/// capacity.
+pub struct BoundedSource <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  def       : channel::Def <CTX>,
+  producer  : (CTX::PID, vec_map::VecMap <std::sync::mpsc::SyncSender <M>>),
+  consumers : vec_map::VecMap <std::sync::mpsc::Receiver <M>>
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 //  traits
 ///////////////////////////////////////////////////////////////////////////////
@@ -59,11 +104,9 @@ pub trait Backend <CTX : session::Context> where
 //
 //  impl Sourcepoint
 //
-// TODO: is there a better way to allow for single-recipient channels and
-// multiple-recipient channels to share the same interface?
-// idea: require that the Message trait has a recipient method? single-recipient
-// channels should verify the recipient matches, but it would require the
-// sender to redundantly set this field in every message
+// Single-recipient channels return `SendError::WrongAddressing` from
+// `send_to` and multiple-recipient channels return it from `send`, as well as
+// from `send_to` a recipient that is not a consumer of the channel.
 
 impl <CTX, M>
   channel::Sourcepoint <CTX> for unbounded_spsc::Sender <M>
@@ -77,10 +120,20 @@ where
     unbounded_spsc::Sender::send (self, M::try_from (message).ok().unwrap())
       .map_err (Into::into)
   }
-  fn send_to (&self, _message : CTX::GMSG, _recipient : CTX::PID)
+  fn send_to (&self, message : CTX::GMSG, _recipient : CTX::PID)
     -> Result <(), channel::SendError <CTX::GMSG>>
   {
-    unimplemented!()  // see TODO above
+    Err (channel::SendError::WrongAddressing (message))
+  }
+  fn try_send (&self, message : CTX::GMSG)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    channel::Sourcepoint::<CTX>::send (self, message).map_err (Into::into)
+  }
+  fn try_send_to (&self, message : CTX::GMSG, _recipient : CTX::PID)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    Err (channel::TrySendError::WrongAddressing (message))
   }
 }
 
@@ -96,10 +149,51 @@ where
     std::sync::mpsc::Sender::send (self, M::try_from (message).ok().unwrap())
       .map_err (Into::into)
   }
-  fn send_to (&self, _message : CTX::GMSG, _recipient : CTX::PID)
+  fn send_to (&self, message : CTX::GMSG, _recipient : CTX::PID)
     -> Result <(), channel::SendError <CTX::GMSG>>
   {
-    unimplemented!()  // see TODO above
+    Err (channel::SendError::WrongAddressing (message))
+  }
+  fn try_send (&self, message : CTX::GMSG)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    channel::Sourcepoint::<CTX>::send (self, message).map_err (Into::into)
+  }
+  fn try_send_to (&self, message : CTX::GMSG, _recipient : CTX::PID)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    Err (channel::TrySendError::WrongAddressing (message))
+  }
+}
+
+impl <CTX, M>
+  channel::Sourcepoint <CTX> for std::sync::mpsc::SyncSender <M>
+where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  fn send (&self, message : CTX::GMSG)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    std::sync::mpsc::SyncSender::send (self, M::try_from (message).ok().unwrap())
+      .map_err (Into::into)
+  }
+  fn send_to (&self, message : CTX::GMSG, _recipient : CTX::PID)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    Err (channel::SendError::WrongAddressing (message))
+  }
+  fn try_send (&self, message : CTX::GMSG)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    std::sync::mpsc::SyncSender::try_send (
+      self, M::try_from (message).ok().unwrap()
+    ).map_err (Into::into)
+  }
+  fn try_send_to (&self, message : CTX::GMSG, _recipient : CTX::PID)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    Err (channel::TrySendError::WrongAddressing (message))
   }
 }
 
@@ -109,19 +203,32 @@ where
   CTX : session::Context,
   M   : Message <CTX>
 {
-  fn send (&self, _message : CTX::GMSG)
+  fn send (&self, message : CTX::GMSG)
     -> Result <(), channel::SendError <CTX::GMSG>>
   {
-    unimplemented!()  // see TODO above
+    Err (channel::SendError::WrongAddressing (message))
   }
   fn send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
     -> Result <(), channel::SendError <CTX::GMSG>>
   {
     let pid : usize = recipient.into();
-    let sender = &self[pid];
+    let Some (sender) = self.get (pid) else {
+      return Err (channel::SendError::WrongAddressing (message))
+    };
     unbounded_spsc::Sender::send (sender, M::try_from(message).ok().unwrap())
       .map_err (Into::into)
   }
+  fn try_send (&self, message : CTX::GMSG)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    Err (channel::TrySendError::WrongAddressing (message))
+  }
+  fn try_send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    channel::Sourcepoint::<CTX>::send_to (self, message, recipient)
+      .map_err (Into::into)
+  }
 }
 
 impl <CTX, M>
@@ -130,19 +237,71 @@ where
   CTX : session::Context,
   M   : Message <CTX>
 {
-  fn send (&self, _message : CTX::GMSG)
+  fn send (&self, message : CTX::GMSG)
     -> Result <(), channel::SendError <CTX::GMSG>>
   {
-    unimplemented!()  // see TODO above
+    Err (channel::SendError::WrongAddressing (message))
   }
   fn send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
     -> Result <(), channel::SendError <CTX::GMSG>>
   {
     let pid : usize = recipient.into();
-    let sender      = &self[pid];
+    let Some (sender) = self.get (pid) else {
+      return Err (channel::SendError::WrongAddressing (message))
+    };
     std::sync::mpsc::Sender::send (sender, M::try_from (message).ok().unwrap())
       .map_err (Into::into)
   }
+  fn try_send (&self, message : CTX::GMSG)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    Err (channel::TrySendError::WrongAddressing (message))
+  }
+  fn try_send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    channel::Sourcepoint::<CTX>::send_to (self, message, recipient)
+      .map_err (Into::into)
+  }
+}
+
+impl <CTX, M>
+  channel::Sourcepoint <CTX> for vec_map::VecMap <std::sync::mpsc::SyncSender <M>>
+where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  fn send (&self, message : CTX::GMSG)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    Err (channel::SendError::WrongAddressing (message))
+  }
+  fn send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    let pid : usize = recipient.into();
+    let Some (sender) = self.get (pid) else {
+      return Err (channel::SendError::WrongAddressing (message))
+    };
+    std::sync::mpsc::SyncSender::send (sender, M::try_from (message).ok().unwrap())
+      .map_err (Into::into)
+  }
+  fn try_send (&self, message : CTX::GMSG)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    Err (channel::TrySendError::WrongAddressing (message))
+  }
+  fn try_send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    let pid : usize = recipient.into();
+    let Some (sender) = self.get (pid) else {
+      return Err (channel::TrySendError::WrongAddressing (message))
+    };
+    std::sync::mpsc::SyncSender::try_send (
+      sender, M::try_from (message).ok().unwrap()
+    ).map_err (Into::into)
+  }
 }
 //  end impl Sourcepoint
 
@@ -164,6 +323,20 @@ where
     unbounded_spsc::Receiver::try_recv (self)
       .map (Into::into).map_err (Into::into)
   }
+  fn recv_timeout (&self, timeout : std::time::Duration)
+    -> Result <CTX::GMSG, channel::RecvTimeoutError>
+  {
+    // the receiver adds the timeout to the current instant unchecked, so a
+    // timeout out of range of the clock blocks as with the other backends
+    match std::time::Instant::now().checked_add (timeout) {
+      Some (deadline) => unbounded_spsc::Receiver::recv_timeout (self,
+        deadline.saturating_duration_since (std::time::Instant::now()))
+        .map (Into::into).map_err (Into::into),
+      None => unbounded_spsc::Receiver::recv (self).map (Into::into)
+        .map_err (|unbounded_spsc::RecvError|
+          channel::RecvTimeoutError::Disconnected)
+    }
+  }
 }
 
 impl <CTX, M>
@@ -180,6 +353,12 @@ where
     std::sync::mpsc::Receiver::try_recv (self)
       .map (Into::into).map_err (Into::into)
   }
+  fn recv_timeout (&self, timeout : std::time::Duration)
+    -> Result <CTX::GMSG, channel::RecvTimeoutError>
+  {
+    std::sync::mpsc::Receiver::recv_timeout (self, timeout)
+      .map (Into::into).map_err (Into::into)
+  }
 }
 //  end impl Endpoint
 
@@ -200,10 +379,13 @@ where
 {
   type Error = channel::CreateError;
   fn try_from (def : channel::Def <CTX>) -> Result <Self, Self::Error> {
+    if def.capacity.is_some() {
+      return Err (channel::CreateError::CapacityMismatch)
+    }
     match def.kind {
       channel::Kind::Simplex => {
         let producer_id = def.producers[0].clone();
-        let consumer_id = def.consumers[0].clone();
+        let consumer_id = def.consumers.first().cloned();
         let (sourcepoint, endpoint) = unbounded_spsc::channel();
         Ok (Simplex {
           def,
@@ -231,14 +413,13 @@ impl <CTX, M> From <Simplex <CTX, M>> for channel::Channel <CTX> where
     );
     let mut endpoints : vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>
       = vec_map::VecMap::new();
-    assert!(
-      endpoints.insert (consumer_id.into(), Box::new (endpoint))
-        .is_none()
-    );
+    let observer = single_consumer (consumer_id, endpoint, &mut endpoints);
     channel::Channel {
       def: simplex.def,
       sourcepoints,
-      endpoints
+      endpoints,
+      injector: None,
+      observer
     }
   }
 }
@@ -256,6 +437,9 @@ where
 {
   type Error = channel::CreateError;
   fn try_from (def : channel::Def <CTX>) -> Result <Self, Self::Error> {
+    if def.capacity.is_some() {
+      return Err (channel::CreateError::CapacityMismatch)
+    }
     match def.kind {
       channel::Kind::Sink => {
         let (sourcepoint, endpoint) = std::sync::mpsc::channel();
@@ -265,11 +449,13 @@ where
             producers.insert (producer_id.clone().into(), sourcepoint.clone())
               .is_none());
         }
-        let consumer_id = def.consumers[0].clone();
+        let injector = def.external().producer.then (|| sourcepoint.clone());
+        let consumer_id = def.consumers.first().cloned();
         Ok (Sink {
           def,
           producers,
-          consumer:  (consumer_id, endpoint)
+          consumer:  (consumer_id, endpoint),
+          injector
         })
       },
       _ => Err (channel::CreateError::KindMismatch)
@@ -291,13 +477,14 @@ impl <CTX, M> From <Sink <CTX, M>> for channel::Channel <CTX> where
     let (consumer_id, endpoint) = sink.consumer;
     let mut endpoints : vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>
       = vec_map::VecMap::new();
-    assert!(
-      endpoints.insert (consumer_id.into(), Box::new (endpoint))
-        .is_none());
+    let observer = single_consumer (consumer_id, endpoint, &mut endpoints);
     channel::Channel {
       def: sink.def,
       sourcepoints,
-      endpoints
+      endpoints,
+      injector: sink.injector
+        .map (|injector| std::sync::Arc::new (injector) as _),
+      observer
     }
   }
 }
@@ -315,6 +502,9 @@ where
 {
   type Error = channel::CreateError;
   fn try_from (def : channel::Def <CTX>) -> Result <Self, Self::Error> {
+    if def.capacity.is_some() {
+      return Err (channel::CreateError::CapacityMismatch)
+    }
     match def.kind {
       channel::Kind::Source => {
         let producer_id = def.producers[0].clone();
@@ -359,19 +549,226 @@ impl <CTX, M> From <Source <CTX, M>> for channel::Channel <CTX> where
     channel::Channel {
       def: source.def,
       sourcepoints,
-      endpoints
+      endpoints,
+      injector: None,
+      observer: None
     }
   }
 }
 //  end impl Source
 
+//
+//  impl BoundedSimplex
+//
+
+impl <CTX, M> Backend <CTX> for BoundedSimplex <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{}
+
+impl <CTX, M>
+  TryFrom <channel::Def <CTX>> for BoundedSimplex <CTX, M>
+where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{
+  type Error = channel::CreateError;
+  fn try_from (def : channel::Def <CTX>) -> Result <Self, Self::Error> {
+    let Some (capacity) = def.capacity else {
+      return Err (channel::CreateError::CapacityMismatch)
+    };
+    match def.kind {
+      channel::Kind::Simplex => {
+        let producer_id = def.producers[0].clone();
+        let consumer_id = def.consumers.first().cloned();
+        let (sourcepoint, endpoint) = std::sync::mpsc::sync_channel (capacity);
+        Ok (BoundedSimplex {
+          def,
+          producer: (producer_id, sourcepoint),
+          consumer: (consumer_id, endpoint)
+        })
+      },
+      _ => Err (channel::CreateError::KindMismatch)
+    }
+  }
+}
+
+impl <CTX, M> From <BoundedSimplex <CTX, M>> for channel::Channel <CTX> where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{
+  fn from (simplex : BoundedSimplex <CTX, M>) -> Self {
+    let (producer_id, sourcepoint) = simplex.producer;
+    let (consumer_id, endpoint)    = simplex.consumer;
+    let mut sourcepoints : vec_map::VecMap <Box <dyn channel::Sourcepoint <CTX>>>
+      = vec_map::VecMap::new();
+    assert!(
+      sourcepoints.insert (producer_id.into(), Box::new (sourcepoint))
+        .is_none()
+    );
+    let mut endpoints : vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>
+      = vec_map::VecMap::new();
+    let observer = single_consumer (consumer_id, endpoint, &mut endpoints);
+    channel::Channel {
+      def: simplex.def,
+      sourcepoints,
+      endpoints,
+      injector: None,
+      observer
+    }
+  }
+}
+//  end impl BoundedSimplex
+
+//
+//  impl BoundedSink
+//
+
+impl <CTX, M> Backend <CTX> for BoundedSink <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{}
+
+impl <CTX, M>
+  TryFrom <channel::Def <CTX>> for BoundedSink <CTX, M>
+where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{
+  type Error = channel::CreateError;
+  fn try_from (def : channel::Def <CTX>) -> Result <Self, Self::Error> {
+    let Some (capacity) = def.capacity else {
+      return Err (channel::CreateError::CapacityMismatch)
+    };
+    match def.kind {
+      channel::Kind::Sink => {
+        let (sourcepoint, endpoint) = std::sync::mpsc::sync_channel (capacity);
+        let mut producers = vec_map::VecMap::new();
+        for producer_id in def.producers.iter() {
+          assert!(
+            producers.insert (producer_id.clone().into(), sourcepoint.clone())
+              .is_none());
+        }
+        let injector = def.external().producer.then (|| sourcepoint.clone());
+        let consumer_id = def.consumers.first().cloned();
+        Ok (BoundedSink {
+          def,
+          producers,
+          consumer:  (consumer_id, endpoint),
+          injector
+        })
+      },
+      _ => Err (channel::CreateError::KindMismatch)
+    }
+  }
+}
+
+impl <CTX, M> From <BoundedSink <CTX, M>> for channel::Channel <CTX> where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{
+  fn from (sink : BoundedSink <CTX, M>) -> Self {
+    let mut sourcepoints : vec_map::VecMap <Box <dyn channel::Sourcepoint <CTX>>>
+      = vec_map::VecMap::new();
+    for (producer_id, sourcepoint) in sink.producers.into_iter() {
+      assert!(sourcepoints.insert (producer_id, Box::new (sourcepoint))
+        .is_none());
+    }
+    let (consumer_id, endpoint) = sink.consumer;
+    let mut endpoints : vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>
+      = vec_map::VecMap::new();
+    let observer = single_consumer (consumer_id, endpoint, &mut endpoints);
+    channel::Channel {
+      def: sink.def,
+      sourcepoints,
+      endpoints,
+      injector: sink.injector
+        .map (|injector| std::sync::Arc::new (injector) as _),
+      observer
+    }
+  }
+}
+//  end impl BoundedSink
+
+//
+//  impl BoundedSource
+//
+
+impl <CTX, M> Backend <CTX> for BoundedSource <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{}
+
+impl <CTX, M>
+  TryFrom <channel::Def <CTX>> for BoundedSource <CTX, M>
+where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{
+  type Error = channel::CreateError;
+  fn try_from (def : channel::Def <CTX>) -> Result <Self, Self::Error> {
+    let Some (capacity) = def.capacity else {
+      return Err (channel::CreateError::CapacityMismatch)
+    };
+    match def.kind {
+      channel::Kind::Source => {
+        let producer_id = def.producers[0].clone();
+        let mut sourcepoints = vec_map::VecMap::new();
+        let mut consumers = vec_map::VecMap::new();
+        for consumer_id in def.consumers.iter() {
+          let (sourcepoint, endpoint) = std::sync::mpsc::sync_channel (capacity);
+          assert!(
+            sourcepoints.insert (consumer_id.clone().into(), sourcepoint)
+              .is_none());
+          assert!(consumers.insert (consumer_id.clone().into(), endpoint)
+            .is_none());
+        }
+        Ok (BoundedSource {
+          def,
+          producer: (producer_id, sourcepoints),
+          consumers
+        })
+      },
+      _ => Err (channel::CreateError::KindMismatch)
+    }
+  }
+}
+
+impl <CTX, M> From <BoundedSource <CTX, M>> for channel::Channel <CTX> where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{
+  fn from (source : BoundedSource <CTX, M>) -> Self {
+    let mut sourcepoints : vec_map::VecMap <Box <dyn channel::Sourcepoint <CTX>>>
+      = vec_map::VecMap::new();
+    let (producer_id, sourcepoint) = source.producer;
+    assert!(
+      sourcepoints.insert (
+        producer_id.into(), Box::new (sourcepoint)
+      ).is_none());
+    let mut endpoints : vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>
+      = vec_map::VecMap::new();
+    for (consumer_id, endpoint) in source.consumers.into_iter() {
+      assert!(endpoints.insert (consumer_id, Box::new (endpoint)).is_none());
+    }
+    channel::Channel {
+      def: source.def,
+      sourcepoints,
+      endpoints,
+      injector: None,
+      observer: None
+    }
+  }
+}
+//  end impl BoundedSource
+
 impl <M, GMSG>
   From <unbounded_spsc::SendError <M>> for channel::SendError <GMSG>
 where
   M : Into <GMSG>
 {
   fn from (send_error : unbounded_spsc::SendError <M>) -> Self {
-    channel::SendError (send_error.0.into())
+    channel::SendError::Disconnected (send_error.0.into())
   }
 }
 
@@ -381,7 +778,22 @@ where
   M : Into <GMSG>
 {
   fn from (send_error : std::sync::mpsc::SendError <M>) -> Self {
-    channel::SendError (send_error.0.into())
+    channel::SendError::Disconnected (send_error.0.into())
+  }
+}
+
+impl <M, GMSG>
+  From <std::sync::mpsc::TrySendError <M>> for channel::TrySendError <GMSG>
+where
+  M : Into <GMSG>
+{
+  fn from (try_send_error : std::sync::mpsc::TrySendError <M>) -> Self {
+    match try_send_error {
+      std::sync::mpsc::TrySendError::Full (message) =>
+        channel::TrySendError::Full (message.into()),
+      std::sync::mpsc::TrySendError::Disconnected (message) =>
+        channel::TrySendError::Disconnected (message.into())
+    }
   }
 }
 
@@ -416,3 +828,48 @@ impl From <std::sync::mpsc::TryRecvError> for channel::TryRecvError {
     }
   }
 }
+
+impl From <unbounded_spsc::RecvTimeoutError> for channel::RecvTimeoutError {
+  fn from (recv_timeout_error : unbounded_spsc::RecvTimeoutError) -> Self {
+    match recv_timeout_error {
+      unbounded_spsc::RecvTimeoutError::Timeout
+        => channel::RecvTimeoutError::Timeout,
+      unbounded_spsc::RecvTimeoutError::Disconnected
+        => channel::RecvTimeoutError::Disconnected
+    }
+  }
+}
+
+impl From <std::sync::mpsc::RecvTimeoutError> for channel::RecvTimeoutError {
+  fn from (recv_timeout_error : std::sync::mpsc::RecvTimeoutError) -> Self {
+    match recv_timeout_error {
+      std::sync::mpsc::RecvTimeoutError::Timeout
+        => channel::RecvTimeoutError::Timeout,
+      std::sync::mpsc::RecvTimeoutError::Disconnected
+        => channel::RecvTimeoutError::Disconnected
+    }
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  functions
+///////////////////////////////////////////////////////////////////////////////
+
+/// Insert the endpoint of the consumer process of a single-consumer channel,
+/// or return it as the observer endpoint if the consumer is external.
+fn single_consumer <CTX, E> (
+  consumer_id : Option <CTX::PID>,
+  endpoint    : E,
+  endpoints   : &mut vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>
+) -> Option <Box <dyn channel::Endpoint <CTX>>> where
+  CTX : session::Context,
+  E   : channel::Endpoint <CTX> + 'static
+{
+  if let Some (consumer_id) = consumer_id {
+    assert!(endpoints.insert (consumer_id.into(), Box::new (endpoint))
+      .is_none());
+    None
+  } else {
+    Some (Box::new (endpoint))
+  }
+}
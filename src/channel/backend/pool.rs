@@ -0,0 +1,267 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+use crate::{channel, session, Message};
+use super::Backend;
+
+///////////////////////////////////////////////////////////////////////////////
+//  structs
+///////////////////////////////////////////////////////////////////////////////
+
+/// An MPMC work queue; each message is received by exactly one consumer,
+/// whichever is the first to be idle.
+///
+/// If a capacity is given, `send` blocks and `try_send` returns
+/// `TrySendError::Full` while the queue holds that many messages.
+pub struct Pool <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  def       : channel::Def <CTX>,
+  producers : vec_map::VecMap <Sender <M>>,
+  consumers : vec_map::VecMap <Receiver <M>>
+}
+
+/// Pool sourcepoint.
+pub struct Sender <M> {
+  shared : Arc <Shared <M>>
+}
+
+/// Pool endpoint.
+pub struct Receiver <M> {
+  shared : Arc <Shared <M>>
+}
+
+struct Shared <M> {
+  queue     : Mutex <Queue <M>>,
+  capacity  : Option <usize>,
+  not_empty : Condvar,
+  not_full  : Condvar
+}
+
+struct Queue <M> {
+  messages : VecDeque <M>,
+  senders  : usize,
+  recvers  : usize
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls
+///////////////////////////////////////////////////////////////////////////////
+
+impl <M> Shared <M> {
+  fn is_full (&self, queue : &Queue <M>) -> bool {
+    self.capacity.is_some_and (|capacity| capacity <= queue.messages.len())
+  }
+}
+
+impl <M> Sender <M> {
+  /// Enqueue a message, blocking while the queue is at capacity.
+  fn push (&self, message : M) -> Result <(), M> {
+    let mut queue = self.shared.queue.lock().unwrap();
+    loop {
+      if queue.recvers == 0 {
+        return Err (message)
+      }
+      if !self.shared.is_full (&queue) {
+        break
+      }
+      queue = self.shared.not_full.wait (queue).unwrap();
+    }
+    queue.messages.push_back (message);
+    drop (queue);
+    self.shared.not_empty.notify_one();
+    Ok (())
+  }
+
+  /// Enqueue a message if the queue is not at capacity.
+  fn try_push (&self, message : M)
+    -> Result <(), std::sync::mpsc::TrySendError <M>>
+  {
+    let mut queue = self.shared.queue.lock().unwrap();
+    if queue.recvers == 0 {
+      return Err (std::sync::mpsc::TrySendError::Disconnected (message))
+    }
+    if self.shared.is_full (&queue) {
+      return Err (std::sync::mpsc::TrySendError::Full (message))
+    }
+    queue.messages.push_back (message);
+    drop (queue);
+    self.shared.not_empty.notify_one();
+    Ok (())
+  }
+}
+
+impl <M> Drop for Sender <M> {
+  fn drop (&mut self) {
+    self.shared.queue.lock().unwrap().senders -= 1;
+    // wake receivers so that they may observe disconnection
+    self.shared.not_empty.notify_all();
+  }
+}
+
+impl <M> Drop for Receiver <M> {
+  fn drop (&mut self) {
+    self.shared.queue.lock().unwrap().recvers -= 1;
+    // wake blocked senders so that they may observe disconnection
+    self.shared.not_full.notify_all();
+  }
+}
+
+impl <CTX, M> channel::Sourcepoint <CTX> for Sender <M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  fn send (&self, message : CTX::GMSG)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    self.push (M::try_from (message).ok().unwrap())
+      .map_err (|message| channel::SendError::Disconnected (message.into()))
+  }
+  fn send_to (&self, message : CTX::GMSG, _recipient : CTX::PID)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    Err (channel::SendError::WrongAddressing (message))
+  }
+  fn try_send (&self, message : CTX::GMSG)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    self.try_push (M::try_from (message).ok().unwrap()).map_err (Into::into)
+  }
+  fn try_send_to (&self, message : CTX::GMSG, _recipient : CTX::PID)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    Err (channel::TrySendError::WrongAddressing (message))
+  }
+}
+
+impl <CTX, M> channel::Endpoint <CTX> for Receiver <M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  fn recv (&self) -> Result <CTX::GMSG, channel::RecvError> {
+    let mut queue = self.shared.queue.lock().unwrap();
+    loop {
+      if let Some (message) = queue.messages.pop_front() {
+        drop (queue);
+        self.shared.not_full.notify_one();
+        return Ok (message.into())
+      }
+      if queue.senders == 0 {
+        return Err (channel::RecvError)
+      }
+      queue = self.shared.not_empty.wait (queue).unwrap();
+    }
+  }
+  fn try_recv (&self) -> Result <CTX::GMSG, channel::TryRecvError> {
+    let mut queue = self.shared.queue.lock().unwrap();
+    match queue.messages.pop_front() {
+      Some (message) => {
+        drop (queue);
+        self.shared.not_full.notify_one();
+        Ok (message.into())
+      }
+      None if queue.senders == 0 => Err (channel::TryRecvError::Disconnected),
+      None => Err (channel::TryRecvError::Empty)
+    }
+  }
+  fn recv_timeout (&self, timeout : std::time::Duration)
+    -> Result <CTX::GMSG, channel::RecvTimeoutError>
+  {
+    match std::time::Instant::now().checked_add (timeout) {
+      Some (deadline) => channel::Endpoint::<CTX>::recv_deadline (self, deadline),
+      None => channel::Endpoint::<CTX>::recv (self)
+        .map_err (|channel::RecvError| channel::RecvTimeoutError::Disconnected)
+    }
+  }
+  fn recv_deadline (&self, deadline : std::time::Instant)
+    -> Result <CTX::GMSG, channel::RecvTimeoutError>
+  {
+    let mut queue = self.shared.queue.lock().unwrap();
+    loop {
+      if let Some (message) = queue.messages.pop_front() {
+        drop (queue);
+        self.shared.not_full.notify_one();
+        return Ok (message.into())
+      }
+      if queue.senders == 0 {
+        return Err (channel::RecvTimeoutError::Disconnected)
+      }
+      let now = std::time::Instant::now();
+      if deadline <= now {
+        return Err (channel::RecvTimeoutError::Timeout)
+      }
+      queue = self.shared.not_empty.wait_timeout (queue, deadline - now)
+        .unwrap().0;
+    }
+  }
+}
+
+impl <CTX, M> Backend <CTX> for Pool <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{}
+
+impl <CTX, M>
+  TryFrom <channel::Def <CTX>> for Pool <CTX, M>
+where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{
+  type Error = channel::CreateError;
+  fn try_from (def : channel::Def <CTX>) -> Result <Self, Self::Error> {
+    match def.kind {
+      channel::Kind::Pool => {
+        let shared = Arc::new (Shared {
+          queue:     Mutex::new (Queue {
+            messages: VecDeque::new(),
+            senders:  def.producers.len(),
+            recvers:  def.consumers.len()
+          }),
+          capacity:  def.capacity,
+          not_empty: Condvar::new(),
+          not_full:  Condvar::new()
+        });
+        let mut producers = vec_map::VecMap::new();
+        for producer_id in def.producers.iter() {
+          assert!(producers.insert (
+            producer_id.clone().into(), Sender { shared: shared.clone() }
+          ).is_none());
+        }
+        let mut consumers = vec_map::VecMap::new();
+        for consumer_id in def.consumers.iter() {
+          assert!(consumers.insert (
+            consumer_id.clone().into(), Receiver { shared: shared.clone() }
+          ).is_none());
+        }
+        Ok (Pool { def, producers, consumers })
+      },
+      _ => Err (channel::CreateError::KindMismatch)
+    }
+  }
+}
+
+impl <CTX, M> From <Pool <CTX, M>> for channel::Channel <CTX> where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{
+  fn from (pool : Pool <CTX, M>) -> Self {
+    let mut sourcepoints : vec_map::VecMap <Box <dyn channel::Sourcepoint <CTX>>>
+      = vec_map::VecMap::new();
+    for (producer_id, sourcepoint) in pool.producers.into_iter() {
+      assert!(sourcepoints.insert (producer_id, Box::new (sourcepoint))
+        .is_none());
+    }
+    let mut endpoints : vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>
+      = vec_map::VecMap::new();
+    for (consumer_id, endpoint) in pool.consumers.into_iter() {
+      assert!(endpoints.insert (consumer_id, Box::new (endpoint)).is_none());
+    }
+    channel::Channel {
+      def: pool.def,
+      sourcepoints,
+      endpoints,
+      injector: None,
+      observer: None
+    }
+  }
+}
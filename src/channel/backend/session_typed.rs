@@ -0,0 +1,194 @@
+use std::sync::{Arc, Mutex};
+use unbounded_spsc;
+use crate::{channel, session, Message};
+use super::Backend;
+
+///////////////////////////////////////////////////////////////////////////////
+//  structs
+///////////////////////////////////////////////////////////////////////////////
+
+/// A bidirectional client/server channel following a `channel::Protocol`.
+///
+/// Messages travel over a pair of SPSC streams. Both sourcepoints share the
+/// protocol state so that a send is checked against the current step before
+/// it is delivered: a message that is sent by the wrong side or that is not
+/// one of the choices for the current step is returned in
+/// `SendError::Protocol`. Receives therefore always observe messages in
+/// protocol order. Once an `End` protocol completes both streams are closed.
+pub struct Session <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  def      : channel::Def <CTX>,
+  producer : (CTX::PID, Sender <M>, unbounded_spsc::Receiver <M>),
+  consumer : (CTX::PID, Sender <M>, unbounded_spsc::Receiver <M>)
+}
+
+/// Session sourcepoint for one side of the channel.
+pub struct Sender <M> {
+  side   : channel::Direction,
+  shared : Arc <Mutex <Shared <M>>>
+}
+
+struct Shared <M> {
+  protocol    : channel::Protocol,
+  /// Current step, or `None` after an `End` protocol has completed
+  step        : Option <usize>,
+  to_consumer : Option <unbounded_spsc::Sender <M>>,
+  to_producer : Option <unbounded_spsc::Sender <M>>
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls
+///////////////////////////////////////////////////////////////////////////////
+
+impl <M> Shared <M> {
+  const fn stream (&mut self, side : channel::Direction)
+    -> &mut Option <unbounded_spsc::Sender <M>>
+  {
+    match side {
+      channel::Direction::ToConsumer => &mut self.to_consumer,
+      channel::Direction::ToProducer => &mut self.to_producer
+    }
+  }
+
+  fn advance (&mut self, step : usize) {
+    let next = step + 1;
+    if next < self.protocol.steps().len() {
+      self.step = Some (next);
+    } else {
+      match self.protocol.ending() {
+        Some (channel::Ending::Repeat) => self.step = Some (0),
+        Some (channel::Ending::End) | None => {
+          self.step        = None;
+          self.to_consumer = None;
+          self.to_producer = None;
+        }
+      }
+    }
+  }
+}
+
+impl <M> Drop for Sender <M> {
+  fn drop (&mut self) {
+    if let Ok (mut shared) = self.shared.lock() {
+      *shared.stream (self.side) = None;
+    }
+  }
+}
+
+impl <CTX, M> channel::Sourcepoint <CTX> for Sender <M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  fn send (&self, message : CTX::GMSG)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    let message = M::try_from (message).ok().unwrap();
+    let mut shared = self.shared.lock().unwrap();
+    let Some (step) = shared.step else {
+      return Err (channel::SendError::Protocol (message.into()))
+    };
+    let name = message.name();
+    if channel::Protocol::direction (step) != self.side
+      || !shared.protocol.steps()[step].contains (&name)
+    {
+      log::debug!(step, message=name.as_str();
+        "session channel protocol violation");
+      return Err (channel::SendError::Protocol (message.into()))
+    }
+    match shared.stream (self.side).as_ref() {
+      Some (sender) => unbounded_spsc::Sender::send (sender, message) ?,
+      None => return Err (channel::SendError::Disconnected (message.into()))
+    }
+    shared.advance (step);
+    drop (shared);
+    Ok (())
+  }
+  fn send_to (&self, message : CTX::GMSG, _recipient : CTX::PID)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    Err (channel::SendError::WrongAddressing (message))
+  }
+  fn try_send (&self, message : CTX::GMSG)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    channel::Sourcepoint::<CTX>::send (self, message).map_err (Into::into)
+  }
+  fn try_send_to (&self, message : CTX::GMSG, _recipient : CTX::PID)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    Err (channel::TrySendError::WrongAddressing (message))
+  }
+}
+
+impl <CTX, M> Backend <CTX> for Session <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{}
+
+impl <CTX, M>
+  TryFrom <channel::Def <CTX>> for Session <CTX, M>
+where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{
+  type Error = channel::CreateError;
+  fn try_from (def : channel::Def <CTX>) -> Result <Self, Self::Error> {
+    if def.capacity.is_some() {
+      return Err (channel::CreateError::CapacityMismatch)
+    }
+    match &def.kind {
+      channel::Kind::Session (protocol) => {
+        let producer_id = def.producers[0].clone();
+        let consumer_id = def.consumers[0].clone();
+        let (to_consumer, consumer_endpoint) = unbounded_spsc::channel();
+        let (to_producer, producer_endpoint) = unbounded_spsc::channel();
+        let shared = Arc::new (Mutex::new (Shared {
+          protocol:    protocol.clone(),
+          step:        Some (0),
+          to_consumer: Some (to_consumer),
+          to_producer: Some (to_producer)
+        }));
+        let producer_sender = Sender {
+          side:   channel::Direction::ToConsumer,
+          shared: shared.clone()
+        };
+        let consumer_sender = Sender {
+          side:   channel::Direction::ToProducer,
+          shared
+        };
+        Ok (Session {
+          def,
+          producer: (producer_id, producer_sender, producer_endpoint),
+          consumer: (consumer_id, consumer_sender, consumer_endpoint)
+        })
+      },
+      _ => Err (channel::CreateError::KindMismatch)
+    }
+  }
+}
+
+impl <CTX, M> From <Session <CTX, M>> for channel::Channel <CTX> where
+  CTX : session::Context,
+  M   : Message <CTX> + 'static
+{
+  fn from (session : Session <CTX, M>) -> Self {
+    let mut sourcepoints : vec_map::VecMap <Box <dyn channel::Sourcepoint <CTX>>>
+      = vec_map::VecMap::new();
+    let mut endpoints : vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>
+      = vec_map::VecMap::new();
+    for (pid, sender, endpoint) in [session.producer, session.consumer] {
+      let pid : usize = pid.into();
+      assert!(sourcepoints.insert (pid, Box::new (sender)).is_none());
+      assert!(endpoints.insert (pid, Box::new (endpoint)).is_none());
+    }
+    channel::Channel {
+      def: session.def,
+      sourcepoints,
+      endpoints,
+      injector: None,
+      observer: None
+    }
+  }
+}
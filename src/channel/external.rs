@@ -0,0 +1,346 @@
+//! Channel roles held by code outside of a session.
+//!
+//! A `Sink` channel listing `external` among its producers can be sent into
+//! by threads that are not processes of the session, such as toolkit
+//! callbacks or test drivers, through the `Injector` handles returned by
+//! `Session::injector`. Injectors can be cloned and sent to other threads;
+//! the consumer sees the channel disconnect only once every injector and
+//! every producer process has been dropped.
+//!
+//! A `Simplex` or `Sink` channel with `external` as its consumer is received
+//! from by the `Observer` returned by `Session::observer` instead of by a
+//! process. An observer is taken once and can be sent to another thread; if
+//! it is not taken before the session runs, sends on the channel fail as if
+//! the consumer had disconnected.
+//!
+//! Injecting events into a session:
+//!
+//! ```
+//! extern crate apis;
+//!
+//! apis::def_session! {
+//!   context Gui {
+//!     PROCESSES where
+//!       let process    = self,
+//!       let message_in = message_in
+//!     [
+//!       process Handler (clicks : u64) -> (u64) {
+//!         kind { apis::process::Kind::asynchronous_default() }
+//!         sourcepoints []
+//!         endpoints    [Events]
+//!         terminate { process.result = process.clicks }
+//!         handle_message {
+//!           let GlobalMessage::Event (Event::Click) = message_in;
+//!           process.clicks += 1;
+//!           apis::process::ControlFlow::Continue
+//!         }
+//!         update { apis::process::ControlFlow::Continue }
+//!       }
+//!     ]
+//!     CHANNELS [
+//!       channel Events <Event> (Sink) {
+//!         producers [external]
+//!         consumers [Handler]
+//!       }
+//!     ]
+//!     MESSAGES [
+//!       message Event { Click }
+//!     ]
+//!   }
+//! }
+//!
+//! fn main() {
+//!   use apis::session::Context;
+//!   let mut session : apis::Session <Gui> = Gui::def().unwrap().into();
+//!   let injector = session.injector (channels::Events);
+//!   let callback = std::thread::spawn (move || {
+//!     for _ in 0..3 {
+//!       injector.send (Event::Click).unwrap();
+//!     }
+//!     // the handler ends once the injector is dropped
+//!   });
+//!   let results = session.run();
+//!   callback.join().unwrap();
+//!   let GlobalPresult::Handler (clicks) =
+//!     results[ProcessId::Handler as usize];
+//!   assert_eq!(clicks, 3);
+//! }
+//! ```
+//!
+//! Observing the output of a session running on another thread:
+//!
+//! ```
+//! extern crate apis;
+//!
+//! apis::def_session! {
+//!   context Counting {
+//!     PROCESSES where
+//!       let process    = self,
+//!       let message_in = message_in
+//!     [
+//!       process Counter (count : u64) {
+//!         kind { apis::process::Kind::anisochronous_default() }
+//!         sourcepoints [Counts]
+//!         endpoints    []
+//!         handle_message { unreachable!() }
+//!         update {
+//!           process.count += 1;
+//!           process.send (ChannelId::Counts, Count::Next (process.count))
+//!             .unwrap();
+//!           if process.count < 3 {
+//!             apis::process::ControlFlow::Continue
+//!           } else {
+//!             apis::process::ControlFlow::Break
+//!           }
+//!         }
+//!       }
+//!     ]
+//!     CHANNELS [
+//!       channel Counts <Count> (Simplex) {
+//!         producers [Counter]
+//!         consumers [external]
+//!       }
+//!     ]
+//!     MESSAGES [
+//!       message Count { Next (u64) }
+//!     ]
+//!   }
+//! }
+//!
+//! fn main() {
+//!   use apis::session::Context;
+//!   let mut session : apis::Session <Counting> =
+//!     Counting::def().unwrap().into();
+//!   let observer = session.observer (channels::Counts);
+//!   let handle = session.spawn().unwrap();
+//!   // iteration ends when the counter disconnects
+//!   let counts = observer.into_iter().map (|Count::Next (count)| count)
+//!     .collect::<Vec <_>>();
+//!   assert_eq!(counts, [1, 2, 3]);
+//!   handle.join();
+//! }
+//! ```
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+use crate::{channel, session, Message};
+
+///////////////////////////////////////////////////////////////////////////////
+//  structs
+///////////////////////////////////////////////////////////////////////////////
+
+/// Sends messages of the local type `M` into a channel with an external
+/// producer from any thread.
+pub struct Injector <CTX : session::Context, M> {
+  channel     : CTX::CID,
+  sourcepoint : Arc <dyn channel::Sourcepoint <CTX> + Sync>,
+  message     : PhantomData <fn (M)>
+}
+
+/// Receives messages of the local type `M` from a channel with an external
+/// consumer.
+pub struct Observer <CTX : session::Context, M> {
+  channel  : CTX::CID,
+  endpoint : Box <dyn channel::Endpoint <CTX>>,
+  message  : PhantomData <fn() -> M>
+}
+
+/// Blocking iterator over the messages received by an `Observer`, ending
+/// when every producer has disconnected.
+#[derive(Debug)]
+pub struct Iter <'a, CTX : session::Context, M> {
+  observer : &'a Observer <CTX, M>
+}
+
+/// Owning version of `Iter`.
+#[derive(Debug)]
+pub struct IntoIter <CTX : session::Context, M> {
+  observer : Observer <CTX, M>
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  traits
+///////////////////////////////////////////////////////////////////////////////
+
+/// Marker for a channel with an external producer.
+///
+/// `def_session!` implements this for the marker in the `channels` module of
+/// each channel that lists `external` among its producers, so that only
+/// those channels are accepted by `Session::injector`.
+pub trait Injectable <CTX : session::Context> : channel::TypedId <CTX> {}
+
+/// Marker for a channel with an external consumer, implemented by
+/// `def_session!` for channels with `external` as their consumer so that only
+/// those channels are accepted by `Session::observer`.
+pub trait Observable <CTX : session::Context> : channel::TypedId <CTX> {}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls
+///////////////////////////////////////////////////////////////////////////////
+
+impl <CTX, M> Injector <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  pub fn new (
+    channel     : CTX::CID,
+    sourcepoint : Arc <dyn channel::Sourcepoint <CTX> + Sync>
+  ) -> Self {
+    Injector { channel, sourcepoint, message: PhantomData }
+  }
+
+  pub const fn channel (&self) -> &CTX::CID {
+    &self.channel
+  }
+
+  /// Send a message, blocking while a bounded channel is full.
+  ///
+  /// # Errors
+  ///
+  /// Returns the message if the consumer has disconnected.
+  pub fn send (&self, message : M)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    self.sourcepoint.send (message.into())
+  }
+
+  /// Send a message without blocking.
+  ///
+  /// # Errors
+  ///
+  /// Returns the message if a bounded channel is full or the consumer has
+  /// disconnected.
+  pub fn try_send (&self, message : M)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    self.sourcepoint.try_send (message.into())
+  }
+}
+
+impl <CTX : session::Context, M> Clone for Injector <CTX, M> {
+  fn clone (&self) -> Self {
+    Injector {
+      channel:     self.channel.clone(),
+      sourcepoint: self.sourcepoint.clone(),
+      message:     PhantomData
+    }
+  }
+}
+
+impl <CTX : session::Context, M> std::fmt::Debug for Injector <CTX, M> {
+  fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
+    f.debug_struct ("Injector").field ("channel", &self.channel).finish()
+  }
+}
+
+impl <CTX, M> Observer <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  pub fn new (channel : CTX::CID, endpoint : Box <dyn channel::Endpoint <CTX>>)
+    -> Self
+  {
+    Observer { channel, endpoint, message: PhantomData }
+  }
+
+  pub const fn channel (&self) -> &CTX::CID {
+    &self.channel
+  }
+
+  /// Block until a message is received.
+  ///
+  /// # Errors
+  ///
+  /// Every producer has disconnected and no messages remain.
+  pub fn recv (&self) -> Result <M, channel::RecvError> {
+    self.endpoint.recv().map (local)
+  }
+
+  /// Receive a message without blocking.
+  ///
+  /// # Errors
+  ///
+  /// No message is queued, or every producer has disconnected and no
+  /// messages remain.
+  pub fn try_recv (&self) -> Result <M, channel::TryRecvError> {
+    self.endpoint.try_recv().map (local)
+  }
+
+  /// Block until a message is received or the timeout elapses.
+  ///
+  /// # Errors
+  ///
+  /// The timeout elapsed, or every producer has disconnected and no messages
+  /// remain.
+  pub fn recv_timeout (&self, timeout : std::time::Duration)
+    -> Result <M, channel::RecvTimeoutError>
+  {
+    self.endpoint.recv_timeout (timeout).map (local)
+  }
+
+  pub const fn iter (&self) -> Iter <'_, CTX, M> {
+    Iter { observer: self }
+  }
+}
+
+impl <CTX : session::Context, M> std::fmt::Debug for Observer <CTX, M> {
+  fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
+    f.debug_struct ("Observer").field ("channel", &self.channel).finish()
+  }
+}
+
+impl <CTX, M> Iterator for Iter <'_, CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  type Item = M;
+  fn next (&mut self) -> Option <M> {
+    self.observer.recv().ok()
+  }
+}
+
+impl <CTX, M> Iterator for IntoIter <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  type Item = M;
+  fn next (&mut self) -> Option <M> {
+    self.observer.recv().ok()
+  }
+}
+
+impl <'a, CTX, M> IntoIterator for &'a Observer <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  type Item     = M;
+  type IntoIter = Iter <'a, CTX, M>;
+  fn into_iter (self) -> Self::IntoIter {
+    self.iter()
+  }
+}
+
+impl <CTX, M> IntoIterator for Observer <CTX, M> where
+  CTX : session::Context,
+  M   : Message <CTX>
+{
+  type Item     = M;
+  type IntoIter = IntoIter <CTX, M>;
+  fn into_iter (self) -> Self::IntoIter {
+    IntoIter { observer: self }
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  functions
+///////////////////////////////////////////////////////////////////////////////
+
+/// The local message received on a channel of the local message type.
+fn local <CTX : session::Context, M : Message <CTX>> (message : CTX::GMSG)
+  -> M
+{
+  let Ok (message) = M::try_from (message) else {
+    unreachable!("channel message should be the local message type")
+  };
+  message
+}
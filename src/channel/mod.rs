@@ -8,6 +8,10 @@ use crate::{session, Message};
 ///////////////////////////////////////////////////////////////////////////////
 
 pub mod backend;
+pub mod external;
+pub mod select;
+
+pub use self::external::{Injectable, Injector, Observable, Observer};
 
 ///////////////////////////////////////////////////////////////////////////////
 //  structs
@@ -17,7 +21,12 @@ pub mod backend;
 pub struct Channel <CTX : session::Context> {
   pub def          : Def <CTX>,
   pub sourcepoints : vec_map::VecMap <Box <dyn Sourcepoint <CTX>>>,
-  pub endpoints    : vec_map::VecMap <Box <dyn Endpoint    <CTX>>>
+  pub endpoints    : vec_map::VecMap <Box <dyn Endpoint    <CTX>>>,
+  /// Sourcepoint shared by the injectors of a channel with an external
+  /// producer
+  pub injector     : Option <std::sync::Arc <dyn Sourcepoint <CTX> + Sync>>,
+  /// Endpoint taken by the observer of a channel with an external consumer
+  pub observer     : Option <Box <dyn Endpoint <CTX>>>
 }
 
 /// Channel definition.
@@ -27,23 +36,99 @@ pub struct Def <CTX : session::Context> {
   kind            : Kind,
   producers       : Vec <CTX::PID>,
   consumers       : Vec <CTX::PID>,
+  /// Bounded channels block on `send` and return `TrySendError::Full` from
+  /// `try_send` when this many messages are queued for a consumer.
+  capacity        : Option <usize>,
+  external        : External,
   message_type_id : CTX::MID
 }
 
+/// Roles of a channel that are held by code outside of the session rather
+/// than by its processes.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct External {
+  /// Messages are sent into the channel with an `Injector`; only supported
+  /// by `Sink` channels, which then need no producer processes.
+  pub producer : bool,
+  /// Messages are received from the channel with an `Observer` instead of a
+  /// consumer process; only supported by `Simplex` and `Sink` channels.
+  pub consumer : bool
+}
+
 /// Sender disconnected, no further messages will ever be received.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct RecvError;
 
-/// Receiver disconnected, message will never be deliverable.
-// NB: this representation may need to be changed if a channel backend is used
-// that doesn't return the message on a send error
-#[derive(Clone, Copy, Eq, PartialEq)]
-pub struct SendError <M> (pub M);
+/// Message protocol of a `Kind::Session` channel.
+///
+/// A protocol is a sequence of steps separated by `;`, each step being a
+/// choice of one or more message variant names separated by `|`. Steps
+/// alternate direction, beginning with a message from the producer (client)
+/// to the consumer (server). The final step is either `End`, after which no
+/// further messages may be sent, or `Repeat`, which returns to the first step:
+///
+/// ```text
+/// Request ; Reply ; End
+/// Get | Put ; Reply | Error ; Repeat
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Protocol {
+  steps  : Vec <Vec <String>>,
+  ending : Option <Ending>
+}
 
 ///////////////////////////////////////////////////////////////////////////////
 //  enums
 ///////////////////////////////////////////////////////////////////////////////
 
+/// Error returned by blocking sends.
+// NB: this representation may need to be changed if a channel backend is used
+// that doesn't return the message on a send error
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum SendError <M> {
+  /// Receiver disconnected, message will never be deliverable.
+  Disconnected (M),
+  /// Message is not permitted by the channel protocol at this step.
+  Protocol (M),
+  /// A single-recipient channel was sent to a specific recipient, a
+  /// multiple-recipient channel was sent without a recipient, or the
+  /// recipient is not a consumer of the channel.
+  WrongAddressing (M)
+}
+
+/// Error returned by non-blocking sends.
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum TrySendError <M> {
+  /// Bounded channel is at capacity, message was not sent.
+  Full (M),
+  /// Receiver disconnected, message will never be deliverable.
+  Disconnected (M),
+  /// Message is not permitted by the channel protocol at this step.
+  Protocol (M),
+  /// A single-recipient channel was sent to a specific recipient, a
+  /// multiple-recipient channel was sent without a recipient, or the
+  /// recipient is not a consumer of the channel.
+  WrongAddressing (M)
+}
+
+/// Final step of a `Protocol`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Ending {
+  /// The channel is closed after the last step.
+  End,
+  /// The protocol returns to the first step after the last step.
+  Repeat
+}
+
+/// Sending side of a step of a `Protocol`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+  /// Producer (client) to consumer (server).
+  ToConsumer,
+  /// Consumer (server) to producer (client).
+  ToProducer
+}
+
 /// Channel kind defines the connection topology of a channel.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Kind {
@@ -76,7 +161,48 @@ pub enum Kind {
   ///  \
   ///   ---->*
   /// ```
-  Source
+  Source,
+
+  /// A source that delivers a clone of each message sent to every consumer.
+  /// Requires the message type to implement `Clone`.
+  ///
+  /// ```text
+  ///   ---->*
+  ///  /
+  /// *----->*
+  ///  \
+  ///   ---->*
+  /// ```
+  Broadcast,
+
+  /// An SPSC buffer holding only the latest value sent; unreceived values
+  /// are overwritten rather than queued.
+  ///
+  /// ```text
+  /// *--[]->*
+  /// ```
+  Buffer,
+
+  /// A bidirectional channel between a single producer (client) and a single
+  /// consumer (server), where messages must follow the given `Protocol`.
+  /// Both processes list the channel as a sourcepoint and as an endpoint.
+  ///
+  /// ```text
+  /// *<---->*
+  /// ```
+  Session (Protocol),
+
+  /// A work queue shared by any number of producers and consumers; each
+  /// message is received by exactly one consumer, whichever is idle first.
+  ///
+  /// ```text
+  /// *-----\   /---->*
+  ///        v /
+  /// *----->*----->*
+  ///        ^ \
+  /// *-----/   \---->*
+  /// ```
+  Pool
 
 }
 
@@ -89,13 +215,21 @@ pub enum DefineError {
   MultipleProducers,
   MultipleConsumers,
   ZeroProducers,
-  ZeroConsumers
+  ZeroConsumers,
+  ZeroCapacity,
+  CapacityUnsupported,
+  InvalidProtocol,
+  ExternalProducerUnsupported,
+  ExternalConsumerUnsupported
 }
 
 /// Error creating concrete `Channel` instance from a given channel def.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum CreateError {
-  KindMismatch
+  /// The backend or constructor does not create channels of the def kind.
+  KindMismatch,
+  /// The backend does not match whether the def is bounded.
+  CapacityMismatch
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -105,6 +239,14 @@ pub enum TryRecvError {
   Disconnected
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecvTimeoutError {
+  /// No message was received before the timeout or deadline elapsed.
+  Timeout,
+  /// Sender disconnected, no further messages will be received.
+  Disconnected
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 //  traits
 ///////////////////////////////////////////////////////////////////////////////
@@ -122,17 +264,146 @@ where
   fn create (_ : Def <CTX>) -> Channel <CTX>;
 }
 
+/// Zero-sized marker for a channel that carries the channel's local message
+/// type, so that sends through `Process::send_typed` and related methods are
+/// checked at compile time.
+///
+/// `def_session!` defines a marker for each channel in a `channels` module
+/// next to `ChannelId`:
+///
+/// ```
+/// extern crate apis;
+/// use apis::{process, Process};
+///
+/// apis::def_session! {
+///   context Mycontext {
+///     PROCESSES where
+///       let process    = self,
+///       let message_in = message_in
+///     [
+///       process A () {
+///         kind           { process::Kind::isochronous_default() }
+///         sourcepoints   [X, Y]
+///         endpoints      []
+///         handle_message { process::ControlFlow::Break }
+///         update         { process.send_typed (channels::X, T::Quit).into() }
+///       }
+///       process B () {
+///         kind           { process::Kind::isochronous_default() }
+///         sourcepoints   []
+///         endpoints      [X, Y]
+///         handle_message { process::ControlFlow::Break }
+///         update         { process::ControlFlow::Break }
+///       }
+///     ]
+///     CHANNELS  [
+///       channel X <T> (Simplex) {
+///         producers [A]
+///         consumers [B]
+///       }
+///       channel Y <U> (Simplex) {
+///         producers [A]
+///         consumers [B]
+///       }
+///     ]
+///     MESSAGES [
+///       message T { Quit }
+///       message U { Quit }
+///     ]
+///   }
+/// }
+///
+/// # fn main() {}
+/// ```
+///
+/// Sending a message of another type on the channel does not compile:
+///
+/// ```compile_fail
+/// # extern crate apis;
+/// # use apis::{process, Process};
+/// # apis::def_session! {
+/// #   context Mycontext {
+/// #     PROCESSES where
+/// #       let process    = self,
+/// #       let message_in = message_in
+/// #     [
+/// #       process A () {
+/// #         kind           { process::Kind::isochronous_default() }
+/// #         sourcepoints   [X, Y]
+/// #         endpoints      []
+/// #         handle_message { process::ControlFlow::Break }
+///           update         { process.send_typed (channels::X, U::Quit).into() }
+/// #       }
+/// #       process B () {
+/// #         kind           { process::Kind::isochronous_default() }
+/// #         sourcepoints   []
+/// #         endpoints      [X, Y]
+/// #         handle_message { process::ControlFlow::Break }
+/// #         update         { process::ControlFlow::Break }
+/// #       }
+/// #     ]
+/// #     CHANNELS  [
+/// #       channel X <T> (Simplex) {
+/// #         producers [A]
+/// #         consumers [B]
+/// #       }
+/// #       channel Y <U> (Simplex) {
+/// #         producers [A]
+/// #         consumers [B]
+/// #       }
+/// #     ]
+/// #     MESSAGES [
+/// #       message T { Quit }
+/// #       message U { Quit }
+/// #     ]
+/// #   }
+/// # }
+/// # fn main() {}
+/// ```
+pub trait TypedId <CTX : session::Context> : Copy {
+  type Message : Message <CTX>;
+  const ID : CTX::CID;
+}
+
 /// Interface for a channel sourcepoint.
+///
+/// Channels with a single recipient per message (`Simplex`, `Sink`, `Buffer`,
+/// `Session`, `Pool`) accept only `send`, and `Source` channels accept only
+/// `send_to`; `Broadcast` channels accept both. Misaddressed sends return the
+/// message in a `WrongAddressing` error.
 pub trait Sourcepoint <CTX : session::Context> : Send {
   fn send    (&self, message : CTX::GMSG) -> Result <(), SendError <CTX::GMSG>>;
   fn send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
     -> Result <(), SendError <CTX::GMSG>>;
+  /// Send without blocking; unbounded channels are never `Full`.
+  fn try_send (&self, message : CTX::GMSG)
+    -> Result <(), TrySendError <CTX::GMSG>>;
+  /// Send to a recipient without blocking; unbounded channels are never
+  /// `Full`.
+  fn try_send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
+    -> Result <(), TrySendError <CTX::GMSG>>;
 }
 
 /// Interface for a channel endpoint.
 pub trait Endpoint <CTX : session::Context> : Send {
   fn recv     (&self) -> Result <CTX::GMSG, RecvError>;
   fn try_recv (&self) -> Result <CTX::GMSG, TryRecvError>;
+  /// Block until a message is received or the timeout elapses.
+  fn recv_timeout (&self, timeout : std::time::Duration)
+    -> Result <CTX::GMSG, RecvTimeoutError>;
+  /// Block until a message is received or the deadline is reached; a
+  /// deadline in the past is equivalent to `try_recv`.
+  fn recv_deadline (&self, deadline : std::time::Instant)
+    -> Result <CTX::GMSG, RecvTimeoutError>
+  {
+    self.recv_timeout (
+      deadline.saturating_duration_since (std::time::Instant::now()))
+  }
+  /// Notifier of an asynchronous process that selects over multiple
+  /// endpoints; `None` by default.
+  fn notifier (&self) -> Option <&select::Notifier> {
+    None
+  }
 }
 
 ///////////////////////////////////////////////////////////////////////////////
@@ -233,6 +504,12 @@ impl <CTX : session::Context> Def <CTX> {
   /// assert_eq!(result, Err (vec![channel::DefineError::MultipleProducers]));
   /// let result = channel::Def::<Mycontext>::define (
   ///   ChannelId::X,
+  ///   channel::Kind::Broadcast,
+  ///   vec![ProcessId::A, ProcessId::B],
+  ///   vec![ProcessId::C, ProcessId::D]);
+  /// assert_eq!(result, Err (vec![channel::DefineError::MultipleProducers]));
+  /// let result = channel::Def::<Mycontext>::define (
+  ///   ChannelId::X,
   ///   channel::Kind::Simplex,
   ///   vec![ProcessId::A],
   ///   vec![ProcessId::B, ProcessId::C]);
@@ -245,10 +522,264 @@ impl <CTX : session::Context> Def <CTX> {
     kind      : Kind,
     producers : Vec <CTX::PID>,
     consumers : Vec <CTX::PID>
+  ) -> Result <Self, Vec <DefineError>> {
+    Self::define_with_capacity (id, kind, producers, consumers, None)
+  }
+
+  /// Define a channel that is bounded if a capacity is given, otherwise
+  /// unbounded. See `define` for validation of producers and consumers.
+  ///
+  /// # Errors
+  ///
+  /// Zero capacity:
+  ///
+  /// ```
+  /// # extern crate apis;
+  /// # use apis::channel;
+  /// # apis::def_session! {
+  /// #   context Mycontext {
+  /// #     PROCESSES where
+  /// #       let process    = self,
+  /// #       let message_in = message_in
+  /// #     [
+  /// #       process A () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #       process B () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #       process C () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #     ]
+  /// #     CHANNELS [
+  /// #       channel X <T> (Simplex) {
+  /// #         producers [A]
+  /// #         consumers [B]
+  /// #       }
+  /// #     ]
+  /// #     MESSAGES [
+  /// #       message T {}
+  /// #     ]
+  /// #   }
+  /// # }
+  /// # fn main() {
+  /// let result = channel::Def::<Mycontext>::define_with_capacity (
+  ///   ChannelId::X,
+  ///   channel::Kind::Simplex,
+  ///   vec![ProcessId::A],
+  ///   vec![ProcessId::B],
+  ///   Some (0));
+  /// assert_eq!(result, Err (vec![channel::DefineError::ZeroCapacity]));
+  /// # }
+  /// ```
+  ///
+  /// Kind does not support a capacity:
+  ///
+  /// ```
+  /// # extern crate apis;
+  /// # use apis::channel;
+  /// # apis::def_session! {
+  /// #   context Mycontext {
+  /// #     PROCESSES where
+  /// #       let process    = self,
+  /// #       let message_in = message_in
+  /// #     [
+  /// #       process A () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #       process B () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #       process C () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #     ]
+  /// #     CHANNELS [
+  /// #       channel X <T> (Simplex) {
+  /// #         producers [A]
+  /// #         consumers [B]
+  /// #       }
+  /// #     ]
+  /// #     MESSAGES [
+  /// #       message T {}
+  /// #     ]
+  /// #   }
+  /// # }
+  /// # fn main() {
+  /// let result = channel::Def::<Mycontext>::define_with_capacity (
+  ///   ChannelId::X,
+  ///   channel::Kind::Broadcast,
+  ///   vec![ProcessId::A],
+  ///   vec![ProcessId::B, ProcessId::C],
+  ///   Some (4));
+  /// assert_eq!(result, Err (vec![channel::DefineError::CapacityUnsupported]));
+  /// # }
+  /// ```
+  pub fn define_with_capacity (
+    id        : CTX::CID,
+    kind      : Kind,
+    producers : Vec <CTX::PID>,
+    consumers : Vec <CTX::PID>,
+    capacity  : Option <usize>
+  ) -> Result <Self, Vec <DefineError>> {
+    Self::define_with_external (
+      id, kind, producers, consumers, capacity, External::default())
+  }
+
+  /// Define a channel with roles held outside of the session. See
+  /// `define_with_capacity`.
+  ///
+  /// # Errors
+  ///
+  /// Kind does not support an external producer:
+  ///
+  /// ```
+  /// # extern crate apis;
+  /// # use apis::channel;
+  /// # apis::def_session! {
+  /// #   context Mycontext {
+  /// #     PROCESSES where
+  /// #       let process    = self,
+  /// #       let message_in = message_in
+  /// #     [
+  /// #       process A () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #       process B () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #       process C () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #     ]
+  /// #     CHANNELS [
+  /// #       channel X <T> (Simplex) {
+  /// #         producers [A]
+  /// #         consumers [B]
+  /// #       }
+  /// #     ]
+  /// #     MESSAGES [
+  /// #       message T {}
+  /// #     ]
+  /// #   }
+  /// # }
+  /// # fn main() {
+  /// let result = channel::Def::<Mycontext>::define_with_external (
+  ///   ChannelId::X,
+  ///   channel::Kind::Simplex,
+  ///   vec![],
+  ///   vec![ProcessId::B],
+  ///   None,
+  ///   channel::External { producer: true, consumer: false });
+  /// assert_eq!(result,
+  ///   Err (vec![channel::DefineError::ExternalProducerUnsupported]));
+  /// # }
+  /// ```
+  ///
+  /// Kind does not support an external consumer:
+  ///
+  /// ```
+  /// # extern crate apis;
+  /// # use apis::channel;
+  /// # apis::def_session! {
+  /// #   context Mycontext {
+  /// #     PROCESSES where
+  /// #       let process    = self,
+  /// #       let message_in = message_in
+  /// #     [
+  /// #       process A () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #       process B () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #       process C () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #     ]
+  /// #     CHANNELS [
+  /// #       channel X <T> (Simplex) {
+  /// #         producers [A]
+  /// #         consumers [B]
+  /// #       }
+  /// #     ]
+  /// #     MESSAGES [
+  /// #       message T {}
+  /// #     ]
+  /// #   }
+  /// # }
+  /// # fn main() {
+  /// let result = channel::Def::<Mycontext>::define_with_external (
+  ///   ChannelId::X,
+  ///   channel::Kind::Source,
+  ///   vec![ProcessId::A],
+  ///   vec![],
+  ///   None,
+  ///   channel::External { producer: false, consumer: true });
+  /// assert_eq!(result,
+  ///   Err (vec![channel::DefineError::ExternalConsumerUnsupported]));
+  /// # }
+  /// ```
+  pub fn define_with_external (
+    id        : CTX::CID,
+    kind      : Kind,
+    producers : Vec <CTX::PID>,
+    consumers : Vec <CTX::PID>,
+    capacity  : Option <usize>,
+    external  : External
   ) -> Result <Self, Vec <DefineError>> {
     let message_type_id = id.message_type_id();
     let def = Def {
-      id, kind, producers, consumers, message_type_id
+      id, kind, producers, consumers, capacity, external, message_type_id
     };
     def.validate_roles() ?;
     Ok (def)
@@ -270,27 +801,125 @@ impl <CTX : session::Context> Def <CTX> {
     &self.consumers
   }
 
-  pub fn to_channel <M> (self) -> Channel <CTX> where
+  pub const fn capacity (&self) -> Option <usize> {
+    self.capacity
+  }
+
+  pub const fn external (&self) -> External {
+    self.external
+  }
+
+  /// Create a channel of any kind other than `Broadcast`.
+  ///
+  /// # Errors
+  ///
+  /// A `Broadcast` channel requires a message type that is `Clone` and is
+  /// created with `to_broadcast_channel` instead:
+  ///
+  /// ```
+  /// # extern crate apis;
+  /// # use apis::channel;
+  /// # apis::def_session! {
+  /// #   context Mycontext {
+  /// #     PROCESSES where
+  /// #       let process    = self,
+  /// #       let message_in = message_in
+  /// #     [
+  /// #       process A () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #       process B () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #       process C () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #     ]
+  /// #     CHANNELS [
+  /// #       channel X <T> (Simplex) {
+  /// #         producers [A]
+  /// #         consumers [B]
+  /// #       }
+  /// #     ]
+  /// #     MESSAGES [
+  /// #       message T {}
+  /// #     ]
+  /// #   }
+  /// # }
+  /// # fn main() {
+  /// let def = channel::Def::<Mycontext>::define (
+  ///   ChannelId::X,
+  ///   channel::Kind::Broadcast,
+  ///   vec![ProcessId::A],
+  ///   vec![ProcessId::B, ProcessId::C]).unwrap();
+  /// assert!(matches!(def.to_channel::<T>(),
+  ///   Err (channel::CreateError::KindMismatch)));
+  /// # }
+  /// ```
+  pub fn to_channel <M> (self) -> Result <Channel <CTX>, CreateError> where
     CTX : 'static,
     M   : Message <CTX> + 'static
   {
-    match self.kind {
-      Kind::Simplex => backend::Simplex::<CTX, M>::try_from (self).unwrap().into(),
-      Kind::Sink    => backend::Sink::<CTX, M>::try_from (self).unwrap().into(),
-      Kind::Source  => backend::Source::<CTX, M>::try_from (self).unwrap().into()
-    }
+    let channel = match (&self.kind, self.capacity) {
+      (Kind::Simplex, None) =>
+        backend::Simplex::<CTX, M>::try_from (self)?.into(),
+      (Kind::Sink,    None) =>
+        backend::Sink::<CTX, M>::try_from (self)?.into(),
+      (Kind::Source,  None) =>
+        backend::Source::<CTX, M>::try_from (self)?.into(),
+      (Kind::Simplex, Some (_)) =>
+        backend::BoundedSimplex::<CTX, M>::try_from (self)?.into(),
+      (Kind::Sink,    Some (_)) =>
+        backend::BoundedSink::<CTX, M>::try_from (self)?.into(),
+      (Kind::Source,  Some (_)) =>
+        backend::BoundedSource::<CTX, M>::try_from (self)?.into(),
+      (Kind::Buffer,  _) =>
+        backend::Buffer::<CTX, M>::try_from (self)?.into(),
+      (Kind::Pool,    _) =>
+        backend::Pool::<CTX, M>::try_from (self)?.into(),
+      (Kind::Session (_), _) =>
+        backend::Session::<CTX, M>::try_from (self)?.into(),
+      (Kind::Broadcast, _) => return Err (CreateError::KindMismatch)
+    };
+    Ok (channel)
+  }
+
+  /// Create a `Broadcast` channel; the message type must be `Clone` so that a
+  /// copy can be delivered to each consumer.
+  ///
+  /// # Errors
+  ///
+  /// The channel kind is not `Broadcast`.
+  pub fn to_broadcast_channel <M> (self) -> Result <Channel <CTX>, CreateError>
+  where
+    CTX : 'static,
+    M   : Message <CTX> + Clone + 'static
+  {
+    Ok (backend::Broadcast::<CTX, M>::try_from (self)?.into())
   }
 
   fn validate_roles (&self) -> Result <(), Vec <DefineError>> {
     let mut errors = Vec::new();
 
     // zero producers
-    if self.producers.is_empty() {
+    if self.producers.is_empty() && !self.external.producer {
       errors.push (DefineError::ZeroProducers);
     }
 
     // zero consumers
-    if self.consumers.is_empty() {
+    if self.consumers.is_empty() && !self.external.consumer {
       errors.push (DefineError::ZeroConsumers);
     }
 
@@ -321,6 +950,33 @@ impl <CTX : session::Context> Def <CTX> {
       errors.push (DefineError::ProducerEqConsumer);
     }
 
+    // zero capacity
+    if self.capacity == Some (0) {
+      errors.push (DefineError::ZeroCapacity);
+    }
+
+    // unbounded-only kinds
+    if matches!(self.kind, Kind::Broadcast | Kind::Buffer | Kind::Session (_))
+      && self.capacity.is_some()
+    {
+      errors.push (DefineError::CapacityUnsupported);
+    }
+
+    // external producers send into a sink
+    if self.external.producer && self.kind != Kind::Sink {
+      errors.push (DefineError::ExternalProducerUnsupported);
+    }
+
+    // an external consumer is the only consumer of a stream or sink
+    if self.external.consumer {
+      if !matches!(self.kind, Kind::Simplex | Kind::Sink) {
+        errors.push (DefineError::ExternalConsumerUnsupported);
+      } else if self.consumers.len() == 1 {
+        // more than one consumer is reported by the kind
+        errors.push (DefineError::MultipleConsumers);
+      }
+    }
+
     // validate channel kind
     if let Err (mut errs)
       = self.kind.validate_roles::<CTX> (&self.producers, &self.consumers)
@@ -344,8 +1000,8 @@ impl Kind {
   ) -> Result <(), Vec <DefineError>> {
     let mut errors = Vec::new();
 
-    match *self {
-      Kind::Simplex => {
+    match self {
+      Kind::Simplex | Kind::Buffer => {
         if 1 < producers.len() {
           errors.push (DefineError::MultipleProducers);
         }
@@ -353,12 +1009,24 @@ impl Kind {
           errors.push (DefineError::MultipleConsumers);
         }
       }
+      Kind::Session (protocol) => {
+        if 1 < producers.len() {
+          errors.push (DefineError::MultipleProducers);
+        }
+        if 1 < consumers.len() {
+          errors.push (DefineError::MultipleConsumers);
+        }
+        if !protocol.is_valid() {
+          errors.push (DefineError::InvalidProtocol);
+        }
+      }
       Kind::Sink => {
         if 1 < consumers.len() {
           errors.push (DefineError::MultipleConsumers);
         }
       }
-      Kind::Source => {
+      Kind::Pool => { /* any number of producers and consumers */ }
+      Kind::Source | Kind::Broadcast => {
         if 1 < producers.len() {
           errors.push (DefineError::MultipleProducers);
         }
@@ -372,29 +1040,216 @@ impl Kind {
     }
   }
 
+  /// Session channels are held as both a sourcepoint and an endpoint by the
+  /// producer and the consumer.
+  pub const fn is_bidirectional (&self) -> bool {
+    matches!(*self, Kind::Session (_))
+  }
+
 } // end impl Kind
 
+impl Protocol {
+  /// Parse a protocol specification of the form `A | B ; C ; End`.
+  ///
+  /// Parsing does not fail; an ill-formed specification, such as one without
+  /// a final `End` or `Repeat`, produces a protocol that is rejected by `Def`
+  /// validation with `DefineError::InvalidProtocol`:
+  ///
+  /// ```
+  /// # extern crate apis;
+  /// use apis::channel::{Ending, Protocol};
+  /// let protocol = Protocol::parse ("Get | Put ; Reply ; Repeat");
+  /// assert_eq!(protocol.steps(), &[vec!["Get", "Put"], vec!["Reply"]]);
+  /// assert_eq!(protocol.ending(), Some (Ending::Repeat));
+  /// let protocol = Protocol::parse ("Request ; Reply");
+  /// assert_eq!(protocol.ending(), None);
+  /// ```
+  pub fn parse (spec : &str) -> Self {
+    let mut steps : Vec <Vec <String>> = spec.split (';')
+      .map (|step| step.split ('|').map (|name| name.trim().to_string())
+        .collect())
+      .collect();
+    let ending = match steps.last().map (Vec::as_slice) {
+      Some ([name]) if name == "End"    => Some (Ending::End),
+      Some ([name]) if name == "Repeat" => Some (Ending::Repeat),
+      _ => None
+    };
+    if ending.is_some() {
+      steps.pop();
+    }
+    Protocol { steps, ending }
+  }
+
+  pub const fn steps (&self) -> &Vec <Vec <String>> {
+    &self.steps
+  }
+
+  pub const fn ending (&self) -> Option <Ending> {
+    self.ending
+  }
+
+  /// Direction of the message sent at the given step.
+  pub const fn direction (step : usize) -> Direction {
+    if step % 2 == 0 {
+      Direction::ToConsumer
+    } else {
+      Direction::ToProducer
+    }
+  }
+
+  /// A valid protocol has at least one step, no empty message names, and
+  /// ends with `End` or `Repeat`.
+  fn is_valid (&self) -> bool {
+    self.ending.is_some() && !self.steps.is_empty()
+      && self.steps.iter().flatten().all (|name| !name.is_empty())
+  }
+}
+
+impl std::fmt::Display for Protocol {
+  fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
+    for step in &self.steps {
+      write!(f, "{} ; ", step.join (" | "))?;
+    }
+    match self.ending {
+      Some (ending) => write!(f, "{ending:?}"),
+      None          => Ok (())
+    }
+  }
+}
+
+impl <T> SendError <T> {
+  /// Recover the message that could not be sent.
+  pub fn into_inner (self) -> T {
+    match self {
+      SendError::Disconnected (message)
+      | SendError::Protocol (message)
+      | SendError::WrongAddressing (message) => message
+    }
+  }
+}
+
 impl <T> std::fmt::Debug for SendError <T> {
   fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
-    "SendError(..)".fmt (f)
+    match *self {
+      SendError::Disconnected (..) => "Disconnected(..)".fmt (f),
+      SendError::Protocol (..)     => "Protocol(..)".fmt (f),
+      SendError::WrongAddressing (..) => "WrongAddressing(..)".fmt (f)
+    }
   }
 }
 
 impl <T> std::fmt::Display for SendError <T> {
   fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
-    "sending on a closed channel".fmt (f)
+    match *self {
+      SendError::Disconnected (..) => "sending on a closed channel".fmt (f),
+      SendError::Protocol (..) => "sending out of protocol order".fmt (f),
+      SendError::WrongAddressing (..) =>
+        "sending with wrong addressing for channel".fmt (f)
+    }
   }
 }
 
 impl <T> std::error::Error for SendError <T> {
   fn description (&self) -> &'static str {
-    "sending on a closed channel"
+    match *self {
+      SendError::Disconnected (..) => "sending on a closed channel",
+      SendError::Protocol (..)     => "sending out of protocol order",
+      SendError::WrongAddressing (..) =>
+        "sending with wrong addressing for channel"
+    }
+  }
+  fn cause (&self) -> Option <&dyn std::error::Error> {
+    None
+  }
+}
+
+impl <T> TrySendError <T> {
+  /// Recover the message that could not be sent.
+  pub fn into_inner (self) -> T {
+    match self {
+      TrySendError::Full (message)
+      | TrySendError::Disconnected (message)
+      | TrySendError::Protocol (message)
+      | TrySendError::WrongAddressing (message) => message
+    }
+  }
+}
+
+impl <T> From <SendError <T>> for TrySendError <T> {
+  fn from (send_error : SendError <T>) -> Self {
+    match send_error {
+      SendError::Disconnected (message) => TrySendError::Disconnected (message),
+      SendError::Protocol (message)     => TrySendError::Protocol (message),
+      SendError::WrongAddressing (message) =>
+        TrySendError::WrongAddressing (message)
+    }
+  }
+}
+
+impl <T> std::fmt::Debug for TrySendError <T> {
+  fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
+    match *self {
+      TrySendError::Full (..)         => "Full(..)".fmt (f),
+      TrySendError::Disconnected (..) => "Disconnected(..)".fmt (f),
+      TrySendError::Protocol (..)     => "Protocol(..)".fmt (f),
+      TrySendError::WrongAddressing (..) => "WrongAddressing(..)".fmt (f)
+    }
+  }
+}
+
+impl <T> std::fmt::Display for TrySendError <T> {
+  fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
+    match *self {
+      TrySendError::Full (..) => "sending on a full channel".fmt (f),
+      TrySendError::Disconnected (..) => "sending on a closed channel".fmt (f),
+      TrySendError::Protocol (..) => "sending out of protocol order".fmt (f),
+      TrySendError::WrongAddressing (..) =>
+        "sending with wrong addressing for channel".fmt (f)
+    }
+  }
+}
+
+impl <T> std::error::Error for TrySendError <T> {
+  fn description (&self) -> &'static str {
+    match *self {
+      TrySendError::Full (..)         => "sending on a full channel",
+      TrySendError::Disconnected (..) => "sending on a closed channel",
+      TrySendError::Protocol (..)     => "sending out of protocol order",
+      TrySendError::WrongAddressing (..) =>
+        "sending with wrong addressing for channel"
+    }
   }
   fn cause (&self) -> Option <&dyn std::error::Error> {
     None
   }
 }
 
+/// A shared sourcepoint, such as the sourcepoint of the injectors of a
+/// channel.
+impl <CTX, S> Sourcepoint <CTX> for std::sync::Arc <S> where
+  CTX : session::Context,
+  S   : Sourcepoint <CTX> + Sync + ?Sized
+{
+  fn send (&self, message : CTX::GMSG) -> Result <(), SendError <CTX::GMSG>> {
+    (**self).send (message)
+  }
+  fn send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
+    -> Result <(), SendError <CTX::GMSG>>
+  {
+    (**self).send_to (message, recipient)
+  }
+  fn try_send (&self, message : CTX::GMSG)
+    -> Result <(), TrySendError <CTX::GMSG>>
+  {
+    (**self).try_send (message)
+  }
+  fn try_send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
+    -> Result <(), TrySendError <CTX::GMSG>>
+  {
+    (**self).try_send_to (message, recipient)
+  }
+}
+
 ///////////////////////////////////////////////////////////////////////////////
 //  functions
 ///////////////////////////////////////////////////////////////////////////////
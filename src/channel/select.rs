@@ -0,0 +1,190 @@
+//! Blocking select over the endpoints of a process.
+//!
+//! When an `Asynchronous` process holds more than one endpoint, or an
+//! `Anisochronous` process parks when idle (`process::Idle::Park`), each of its
+//! endpoints is wrapped in a `Notified` endpoint sharing a single `Notifier`,
+//! and every sourcepoint of those channels is wrapped in a `Notifying`
+//! sourcepoint, including the sourcepoint shared by injectors of an external
+//! producer. A successful send or a disconnecting sourcepoint wakes the
+//! process, which then polls each of its endpoints in turn.
+
+use std::sync::{Arc, Condvar, Mutex};
+use vec_map::VecMap;
+use crate::{channel, session};
+
+///////////////////////////////////////////////////////////////////////////////
+//  structs
+///////////////////////////////////////////////////////////////////////////////
+
+/// Wakes a process waiting on any of its endpoints.
+///
+/// Each notification increments a generation counter; a waiter records the
+/// generation before polling its endpoints and then waits for it to change,
+/// so a notification that arrives during the poll is never missed.
+#[derive(Clone, Debug, Default)]
+pub struct Notifier {
+  shared : Arc <(Mutex <u64>, Condvar)>
+}
+
+/// Sourcepoint that notifies consumers after each send and on disconnection.
+pub struct Notifying <S : ?Sized> {
+  /// Always `Some` until dropped
+  sourcepoint : Option <Box <S>>,
+  notifiers   : Vec <Notifier>
+}
+
+/// Endpoint carrying the notifier of the process that holds it.
+pub struct Notified <CTX : session::Context> {
+  endpoint : Box <dyn channel::Endpoint <CTX>>,
+  notifier : Notifier
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls
+///////////////////////////////////////////////////////////////////////////////
+
+impl Notifier {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Current generation; pass to `wait` after polling.
+  pub fn generation (&self) -> u64 {
+    *self.shared.0.lock().unwrap()
+  }
+
+  pub fn notify (&self) {
+    let (generation, condvar) = &*self.shared;
+    *generation.lock().unwrap() += 1;
+    condvar.notify_all();
+  }
+
+  /// Block until a notification after the given generation.
+  pub fn wait (&self, generation : u64) {
+    let (current, condvar) = &*self.shared;
+    let current = condvar.wait_while (current.lock().unwrap(),
+      |current| *current == generation
+    ).unwrap();
+    drop (current);
+  }
+
+  /// Block until a notification after the given generation or until the
+  /// timeout elapses.
+  pub fn wait_timeout (&self, generation : u64, timeout : std::time::Duration) {
+    let (current, condvar) = &*self.shared;
+    let (current, _timeout) = condvar.wait_timeout_while (
+      current.lock().unwrap(), timeout, |current| *current == generation
+    ).unwrap();
+    drop (current);
+  }
+}
+
+impl <S : ?Sized> Notifying <S> {
+  fn sourcepoint (&self) -> &S {
+    self.sourcepoint.as_deref().unwrap()
+  }
+
+  fn notify (&self) {
+    self.notifiers.iter().for_each (Notifier::notify);
+  }
+}
+
+impl <S : ?Sized> Drop for Notifying <S> {
+  fn drop (&mut self) {
+    // the sourcepoint must be dropped before notifying so that consumers
+    // observe the disconnection
+    drop (self.sourcepoint.take());
+    self.notify();
+  }
+}
+
+impl <CTX, S> channel::Sourcepoint <CTX> for Notifying <S> where
+  CTX : session::Context,
+  S   : channel::Sourcepoint <CTX> + ?Sized
+{
+  fn send (&self, message : CTX::GMSG)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    self.sourcepoint().send (message).inspect (|()| self.notify())
+  }
+  fn send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
+    -> Result <(), channel::SendError <CTX::GMSG>>
+  {
+    self.sourcepoint().send_to (message, recipient).inspect (|()| self.notify())
+  }
+  fn try_send (&self, message : CTX::GMSG)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    self.sourcepoint().try_send (message).inspect (|()| self.notify())
+  }
+  fn try_send_to (&self, message : CTX::GMSG, recipient : CTX::PID)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  {
+    self.sourcepoint().try_send_to (message, recipient)
+      .inspect (|()| self.notify())
+  }
+}
+
+impl <CTX : session::Context> channel::Endpoint <CTX> for Notified <CTX> {
+  fn recv (&self) -> Result <CTX::GMSG, channel::RecvError> {
+    self.endpoint.recv()
+  }
+  fn try_recv (&self) -> Result <CTX::GMSG, channel::TryRecvError> {
+    self.endpoint.try_recv()
+  }
+  fn recv_timeout (&self, timeout : std::time::Duration)
+    -> Result <CTX::GMSG, channel::RecvTimeoutError>
+  {
+    self.endpoint.recv_timeout (timeout)
+  }
+  fn recv_deadline (&self, deadline : std::time::Instant)
+    -> Result <CTX::GMSG, channel::RecvTimeoutError>
+  {
+    self.endpoint.recv_deadline (deadline)
+  }
+  fn notifier (&self) -> Option <&Notifier> {
+    Some (&self.notifier)
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  functions
+///////////////////////////////////////////////////////////////////////////////
+
+/// Wrap the endpoints held by processes with a notifier, and if there are any
+/// such endpoints, wrap every sourcepoint of the channel and its injector
+/// sourcepoint to notify them.
+pub fn notify_endpoints <CTX : session::Context + 'static> (
+  channel   : &mut channel::Channel <CTX>,
+  notifiers : &VecMap <Notifier>
+) {
+  let mut channel_notifiers = Vec::new();
+  channel.endpoints = std::mem::take (&mut channel.endpoints).into_iter()
+    .map (|(pid, endpoint)| match notifiers.get (pid) {
+      Some (notifier) => {
+        channel_notifiers.push (notifier.clone());
+        let notified : Box <dyn channel::Endpoint <CTX>> =
+          Box::new (Notified { endpoint, notifier: notifier.clone() });
+        (pid, notified)
+      }
+      None => (pid, endpoint)
+    }).collect();
+  if channel_notifiers.is_empty() {
+    return
+  }
+  channel.sourcepoints = std::mem::take (&mut channel.sourcepoints).into_iter()
+    .map (|(pid, sourcepoint)| {
+      let notifying : Box <dyn channel::Sourcepoint <CTX>> =
+        Box::new (Notifying {
+          sourcepoint: Some (sourcepoint),
+          notifiers:   channel_notifiers.clone()
+        });
+      (pid, notifying)
+    }).collect();
+  channel.injector = channel.injector.take().map (|injector|
+    Arc::new (Notifying {
+      sourcepoint: Some (Box::new (injector)),
+      notifiers:   channel_notifiers
+    }) as _
+  );
+}
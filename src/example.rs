@@ -28,7 +28,12 @@ pub mod session_a {
       [
         process Chargen (update_count : u64) {
           kind {
-            process::Kind::Isochronous { tick_ms: 20, ticks_per_update: 1 }
+            process::Kind::Isochronous {
+              tick:             std::time::Duration::from_millis (20),
+              ticks_per_update: 1,
+              sleep:            process::Sleep::Thread,
+              catch_up:         process::CatchUp::All
+            }
           }
           sourcepoints   [Charstream]
           endpoints      []
@@ -126,7 +131,12 @@ pub mod session_b {
         //
         process SeqGen (update_count : u64) {
           kind {
-            process::Kind::Isochronous { tick_ms: 20, ticks_per_update: 1 }
+            process::Kind::Isochronous {
+              tick:             std::time::Duration::from_millis (20),
+              ticks_per_update: 1,
+              sleep:            process::Sleep::Thread,
+              catch_up:         process::CatchUp::All
+            }
           }
           sourcepoints   [Seqints]
           endpoints      []
@@ -0,0 +1,253 @@
+//! Time source of process run loops.
+//!
+//! Processes read the current time and wait for ticks, timers, and idle
+//! backoff through the `Clock` of their session. The default `System` clock
+//! uses `std::time::Instant` and the `Sleep` strategy of the process kind. A
+//! `Virtual` clock only moves forward when advanced, either manually or
+//! automatically once every process waiting on the clock is asleep, so that
+//! sessions of timed processes can be run in a fraction of their real
+//! duration with exact tick counts.
+//!
+//! Processes that block on channels (`Asynchronous`, or `Anisochronous` with
+//! `Idle::Park`) are not waiting on the clock: they read virtual time while
+//! handling messages, but the order in which they run relative to timed
+//! processes is not controlled by the clock.
+
+use std::sync::{Arc, Condvar, Mutex};
+use std::time;
+use crate::process;
+
+///////////////////////////////////////////////////////////////////////////////
+//  structs
+///////////////////////////////////////////////////////////////////////////////
+
+/// The system clock.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct System;
+
+/// A clock that is advanced manually or automatically.
+///
+/// A manual clock is advanced by a test driving the session from another
+/// thread, calling `wait_idle` before each `advance` so that every timed
+/// process has finished its tick.
+///
+/// Run a session of timed processes on an automatically advancing clock:
+///
+/// ```
+/// extern crate apis;
+///
+/// apis::def_session! {
+///   context Ticker {
+///     PROCESSES where
+///       let process    = self,
+///       let message_in = message_in
+///     [
+///       process Counter (ticks : u64) -> (u64) {
+///         kind {
+///           apis::process::Kind::Isochronous {
+///             tick:             std::time::Duration::from_secs (1),
+///             ticks_per_update: 1,
+///             sleep:            apis::process::Sleep::Thread,
+///             catch_up:         apis::process::CatchUp::All
+///           }
+///         }
+///         sourcepoints   []
+///         endpoints      []
+///         terminate      { process.result = process.ticks }
+///         handle_message { unreachable!() }
+///         update {
+///           process.ticks += 1;
+///           if process.ticks < 60 {
+///             apis::process::ControlFlow::Continue
+///           } else {
+///             apis::process::ControlFlow::Break
+///           }
+///         }
+///       }
+///     ]
+///     CHANNELS  []
+///     MESSAGES  []
+///   }
+/// }
+///
+/// fn main() {
+///   use apis::session::Context;
+///   use apis::process::clock;
+///   let clock = clock::Virtual::auto();
+///   let t_start = std::time::Instant::now();
+///   let session : apis::Session <Ticker> = Ticker::def().unwrap().into();
+///   let mut session =
+///     session.with_clock (std::sync::Arc::new (clock.clone()));
+///   let results = session.run();
+///   let GlobalPresult::Counter (ticks) = results[ProcessId::Counter as usize];
+///   assert_eq!(ticks, 60);
+///   // the last update is on the tick at 59 seconds
+///   assert_eq!(clock.elapsed(), std::time::Duration::from_secs (59));
+///   assert!(t_start.elapsed() < std::time::Duration::from_secs (1));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Virtual {
+  shared : Arc <(Mutex <VirtualState>, Condvar)>
+}
+
+#[derive(Debug)]
+struct VirtualState {
+  start        : time::Instant,
+  now          : time::Instant,
+  /// Advance to the earliest deadline when every participant is asleep
+  auto_advance : bool,
+  /// Processes registered to wait on the clock
+  participants : usize,
+  /// Deadlines of participants that are asleep
+  sleeping     : Vec <time::Instant>
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  traits
+///////////////////////////////////////////////////////////////////////////////
+
+/// Time source of process run loops.
+pub trait Clock : Send + Sync + std::fmt::Debug {
+  fn now (&self) -> time::Instant;
+
+  /// Block until the given deadline. A real-time clock waits with the given
+  /// sleep strategy.
+  fn sleep_until (&self, deadline : time::Instant, sleep : process::Sleep);
+
+  /// Called by the session for each process that waits on the clock with
+  /// `sleep_until`, before any process is started. Does nothing by default.
+  fn register (&self) { }
+
+  /// Called when the run loop of a registered process ends. Does nothing by
+  /// default.
+  fn unregister (&self) { }
+
+  /// Instant on the system clock at which the given deadline on this clock
+  /// is reached if both advance at the same rate, for waits that can only
+  /// block on the system clock such as channel receives.
+  fn system_deadline (&self, deadline : time::Instant) -> time::Instant {
+    time::Instant::now() + deadline.saturating_duration_since (self.now())
+  }
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls
+///////////////////////////////////////////////////////////////////////////////
+
+impl Clock for System {
+  fn now (&self) -> time::Instant {
+    time::Instant::now()
+  }
+
+  fn sleep_until (&self, deadline : time::Instant, sleep : process::Sleep) {
+    sleep.until (deadline)
+  }
+
+  fn system_deadline (&self, deadline : time::Instant) -> time::Instant {
+    deadline
+  }
+}
+
+impl Virtual {
+  /// A clock that only advances when `advance` is called.
+  pub fn new() -> Self {
+    Self::with_auto_advance (false)
+  }
+
+  /// A clock that advances to the earliest deadline whenever every process
+  /// registered with the clock is asleep.
+  pub fn auto() -> Self {
+    Self::with_auto_advance (true)
+  }
+
+  fn with_auto_advance (auto_advance : bool) -> Self {
+    let start = time::Instant::now();
+    let state = VirtualState {
+      start, now: start, auto_advance, participants: 0, sleeping: Vec::new()
+    };
+    Virtual { shared: Arc::new ((Mutex::new (state), Condvar::new())) }
+  }
+
+  /// Time advanced since the clock was created.
+  pub fn elapsed (&self) -> time::Duration {
+    let state = self.shared.0.lock().unwrap();
+    state.now - state.start
+  }
+
+  /// Advance the clock, waking processes whose deadlines have been reached.
+  pub fn advance (&self, duration : time::Duration) {
+    let (state, condvar) = &*self.shared;
+    state.lock().unwrap().now += duration;
+    condvar.notify_all();
+  }
+
+  /// Block until every process registered with the clock is asleep, or no
+  /// registered processes remain.
+  pub fn wait_idle (&self) {
+    let (state, condvar) = &*self.shared;
+    let state = condvar.wait_while (state.lock().unwrap(), |state| {
+      // a woken process may not have removed its deadline yet
+      let asleep = state.sleeping.iter()
+        .filter (|deadline| state.now < **deadline).count();
+      asleep < state.participants
+    }).unwrap();
+    drop (state);
+  }
+}
+
+impl Default for Virtual {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Clock for Virtual {
+  fn now (&self) -> time::Instant {
+    self.shared.0.lock().unwrap().now
+  }
+
+  fn sleep_until (&self, deadline : time::Instant, _sleep : process::Sleep) {
+    let (state, condvar) = &*self.shared;
+    let mut state = state.lock().unwrap();
+    if state.now < deadline {
+      state.sleeping.push (deadline);
+      state.try_advance (condvar);
+      condvar.notify_all();
+      state = condvar.wait_while (state, |state| state.now < deadline).unwrap();
+      let index = state.sleeping.iter()
+        .position (|sleeping| *sleeping == deadline).unwrap();
+      state.sleeping.swap_remove (index);
+    }
+    drop (state);
+  }
+
+  fn register (&self) {
+    self.shared.0.lock().unwrap().participants += 1;
+  }
+
+  fn unregister (&self) {
+    let (state, condvar) = &*self.shared;
+    let mut state = state.lock().unwrap();
+    state.participants -= 1;
+    state.try_advance (condvar);
+    drop (state);
+    condvar.notify_all();
+  }
+}
+
+impl VirtualState {
+  /// When auto advancing and every participant is asleep, advance to the
+  /// earliest deadline and wake the participants. Deadlines of woken
+  /// processes that have not yet been removed are already reached and do not
+  /// advance the clock.
+  fn try_advance (&mut self, condvar : &Condvar) {
+    if self.auto_advance && 0 < self.participants
+      && self.sleeping.len() == self.participants
+    {
+      let earliest = self.sleeping.iter().min().copied().unwrap();
+      self.now = self.now.max (earliest);
+      condvar.notify_all();
+    }
+  }
+}
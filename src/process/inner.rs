@@ -1,6 +1,13 @@
 //! The `sourcepoints` field is wrapped in a `Refcell` and an `Option` so that
 //! it may be "removed" from the process with `take_endpoints` while the run
 //! loop receives messages.
+//!
+//! A running process is `Paused` while blocked by a pause request of its
+//! session, and `Abort` ends a process from any state. `Reset` returns an
+//! ended process to `Ready` so that it can be restarted by its supervisor.
+
+// the generated `ExtendedState::new` takes an argument for each field
+#![expect(clippy::too_many_arguments)]
 
 use {std, vec_map};
 use macro_machines::def_machine_nodefault;
@@ -12,23 +19,33 @@ def_machine_nodefault! {
     session_handle : session::Handle <CTX>,
     sourcepoints   : vec_map::VecMap <Box <dyn channel::Sourcepoint <CTX>>>,
     endpoints      : std::cell::RefCell <Option <
-      vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>>>
+      vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>>>,
+    update_context : process::UpdateContext = Default::default(),
+    timers         : process::Timers        = Default::default(),
+    clock          : std::sync::Arc <dyn process::Clock>
+      = std::sync::Arc::new (process::clock::System),
+    supervisor     : std::sync::Arc <process::Supervisor> = Default::default(),
+    generation     : u64 = 0
   ) @ _inner {
     STATES [
       state Ready   ()
       state Running ()
+      state Paused  ()
       state Ended   ()
     ]
     EVENTS [
-      event Run <Ready>   => <Running> ()
-      event End <Running> => <Ended>   ()
+      event Run    <Ready>   => <Running> ()
+      event End    <Running> => <Ended>   ()
+      event Pause  <Running> => <Paused>  ()
+      event Resume <Paused>  => <Running> ()
+      event Abort  <*>       => <Ended>   ()
+      event Reset  <Ended>   => <Ready>   () {} => {
+        *update_context = Default::default();
+        *timers         = Default::default();
+      }
       //event Tick    <Running> => <Running> ()
       //event Update  <Running> => <Running> ()
       //event Message <Running> => <Running> ()
-      //event Abort   <Ready>   => <Ended>   ()
-      //event Reset   <Running> => <Ready>   ()
-      //event Resume  <Ended>   => <Running> ()
-      //event Restart <Ended>   => <Ready>   ()
     ]
     initial_state:  Ready
     terminal_state: Ended {
@@ -6,11 +6,16 @@ use std::sync::mpsc;
 use std::time;
 use vec_map::VecMap;
 
+pub mod clock;
 pub mod inner;
 pub mod presult;
+pub mod step;
+pub mod supervisor;
 
+pub use self::clock::Clock;
 pub use self::inner::Inner;
 pub use self::presult::Presult;
+pub use self::supervisor::Supervisor;
 
 ////////////////////////////////////////////////////////////////////////////////
 //  structs                                                                   //
@@ -22,12 +27,14 @@ pub struct Def <CTX : session::Context> {
   id           : CTX::PID,
   kind         : Kind,
   sourcepoints : Vec <CTX::CID>,
-  endpoints    : Vec <CTX::CID>
+  endpoints    : Vec <CTX::CID>,
+  poll         : Poll,
+  supervision  : Supervision
 }
 
 /// Handle to a process held by the session.
 pub struct Handle <CTX : session::Context> {
-  pub result_rx        : mpsc::Receiver <CTX::GPRES>,
+  pub result_rx        : mpsc::Receiver <Outcome <CTX::GPRES>>,
   pub continuation_tx  : mpsc::Sender <session::Continuation <CTX>>,
   /// When the session drops, the `finish` method will either join or send
   /// a continuation depending on the contents of this field.
@@ -35,45 +42,124 @@ pub struct Handle <CTX : session::Context> {
     std::thread::JoinHandle <Option <()>>, Option <session::Continuation <CTX>>>
 }
 
+/// Timing information for the current call to `Process::update`, available
+/// through `Process::update_context`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct UpdateContext {
+  /// Number of updates before this one
+  pub update       : u64,
+  /// Index of the tick on which the update occurs; untimed processes count
+  /// one tick per update
+  pub tick         : u64,
+  /// Time elapsed since the previous update, or since the process started
+  pub dt           : time::Duration,
+  /// Time by which the tick started after it was scheduled; zero for untimed
+  /// processes
+  pub lateness     : time::Duration,
+  /// Messages handled on this tick, or since the previous update for
+  /// asynchronous processes
+  pub messages     : u64,
+  /// Ticks skipped since the previous update by an `Isochronous` process with
+  /// the `CatchUp::Report` policy
+  pub missed_ticks : u64
+}
+
+/// Supervision of a process by its session, declared with `supervise` in
+/// `def_session!` (see `supervisor`).
+///
+/// A process is restarted at most `max_restarts` times within any `period`;
+/// once the restart intensity is exceeded, the process ends as if it were not
+/// supervised.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Supervision {
+  pub restart      : Restart,
+  pub strategy     : Strategy,
+  pub max_restarts : u32,
+  pub period       : time::Duration
+}
+
+/// Identifies a timer set with `Process::set_timer` or
+/// `Process::set_interval`.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct TimerId (u64);
+
+/// Pending timers of a process.
+#[derive(Debug, Default)]
+pub struct Timers {
+  next_id : u64,
+  pending : Vec <Timer>
+}
+
+#[derive(Debug)]
+struct Timer {
+  id       : TimerId,
+  /// `None` if the deadline is too far in the future to be represented
+  deadline : Option <time::Instant>,
+  /// Period of a repeating timer
+  interval : Option <time::Duration>
+}
+
+/// Unregisters a clocked process from the session clock when dropped at the
+/// end of its run loop, including when the run loop panics.
+struct ClockRegistration (Option <std::sync::Arc <dyn Clock>>);
+
+/// Polling state of a polling process run loop derived from its `Poll` policy.
+struct Poller {
+  /// Messages to receive from each endpoint per round in endpoint iteration
+  /// order; `None` receives until the endpoint is empty
+  quotas       : smallvec::SmallVec <[Option <u32>; 8]>,
+  /// Messages remaining to receive per tick
+  budget       : Option <u32>,
+  /// Endpoint iteration index at which to start polling
+  next_channel : usize,
+  /// Poll a single round
+  drain        : bool
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 //  enums                                                                     //
 ////////////////////////////////////////////////////////////////////////////////
 
 /// Specifies the loop behavior of a process.
 ///
-/// - `Asynchronous` is a loop that blocks waiting on exactly one channel
-///   endpoint.
+/// - `Asynchronous` is a loop that blocks waiting on one or more channel
+///   endpoints.
 /// - `Isochronous` is a fixed-timestep loop in which endpoints are polled
 ///   once per 'tick' and will attempt to "catch up" if it falls behind.
 /// - `Mesochronous` is a rate-limited loop that polls processes and loops
 ///   immediately if enough time has passed and otherwise sleeps for the
 ///   remaining duration until the next 'tick'.
-/// - `Anisochronous` is an un-timed polling loop which always loops immediately
-///   and always processes one update per tick.
+/// - `Anisochronous` is an un-timed polling loop which processes one update per
+///   tick and loops according to its `Idle` strategy.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum Kind {
   /// Block waiting on one or more endpoints.
   ///
-  /// Asynchronous processes can only hold multiple endpoints of compatible
-  /// kinds of channels. Currently this is either any number of sink endpoints,
-  /// or else any number and combination of simplex or source endpoints. This is
-  /// validated internally when defining an `Def` struct with the provided
-  /// kind and endpoints.
+  /// An asynchronous process with multiple endpoints blocks until a message
+  /// arrives on any of them (see `channel::select`).
+  ///
+  /// If an idle timeout is given, `Process::handle_timeout` is called each
+  /// time the timeout elapses without a message being received. A timeout
+  /// too large to be represented by the clock never elapses.
   Asynchronous {
-    messages_per_update : u32
+    messages_per_update : u32,
+    idle_timeout        : Option <time::Duration>
   },
 
   /// A fixed-time step polling loop that will try to "catch up" if it falls
   /// behind.
   Isochronous {
-    tick_ms          : u32,
-    ticks_per_update : u32
+    tick             : time::Duration,
+    ticks_per_update : u32,
+    sleep            : Sleep,
+    catch_up         : CatchUp
   },
 
   /// A rate-limited polling loop.
   Mesochronous {
-    tick_ms          : u32,
-    ticks_per_update : u32
+    tick             : time::Duration,
+    ticks_per_update : u32,
+    sleep            : Sleep
   },
 
   /// Poll to exhaustion and update immediately.
@@ -84,8 +170,133 @@ pub enum Kind {
   /// Note that unlike other polling process kinds (`Isochronous` and
   /// `Mesochronous`), ticks and updates are always one-to-one since an
   /// external blocking mechanism is expected to be used in the `update`
-  /// function.
-  Anisochronous
+  /// function. When no external blocking mechanism is used, the `Idle`
+  /// strategy determines how the loop waits after a tick in which no
+  /// messages were received.
+  Anisochronous {
+    idle : Idle
+  }
+}
+
+/// Strategy used by `Anisochronous` processes after a tick in which no
+/// messages were received.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Idle {
+  /// Loop immediately.
+  #[default]
+  Spin,
+  /// Yield the thread to the OS scheduler before looping.
+  Yield,
+  /// Sleep before looping, starting at `min` and doubling on each
+  /// consecutive idle tick up to `max`. A `max` of `Duration::MAX` leaves the
+  /// sleep uncapped; it stops growing once the wake time is out of range of
+  /// the clock.
+  Backoff {
+    min : time::Duration,
+    max : time::Duration
+  },
+  /// Block until a message arrives on any endpoint or a sender disconnects
+  /// (see `channel::select`).
+  Park
+}
+
+/// Strategy used by timed processes (`Isochronous` and `Mesochronous`) to wait
+/// for the next tick.
+///
+/// Thread sleeps are only as precise as the OS scheduler allows, typically
+/// waking tens of microseconds to a millisecond late. For tick rates in the
+/// kilohertz range, `Hybrid` sleeps until shortly before the tick and then
+/// busy-waits for the remainder, and `Spin` busy-waits for the entire time at
+/// the cost of occupying a core.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Sleep {
+  /// Sleep the thread until the next tick.
+  #[default]
+  Thread,
+  /// Busy-wait until the next tick.
+  Spin,
+  /// Sleep the thread until `spin` before the next tick, then busy-wait.
+  Hybrid {
+    spin : time::Duration
+  }
+}
+
+/// Policy of an `Isochronous` process for ticks that were missed because the
+/// process fell behind, e.g. after a long update.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum CatchUp {
+  /// Process every missed tick back to back.
+  #[default]
+  All,
+  /// Process at most the given number of missed ticks, skipping the rest.
+  Limit {
+    ticks : u32
+  },
+  /// Skip all missed ticks and continue from the current tick.
+  Skip,
+  /// Skip all missed ticks and report the number skipped to the next update
+  /// in `UpdateContext::missed_ticks`.
+  Report
+}
+
+/// Order in which polling processes (`Isochronous`, `Mesochronous`, and
+/// `Anisochronous`) receive messages from their endpoints on each tick.
+///
+/// Except for `Drain`, endpoints are polled in rounds that take a limited
+/// number of messages from each open channel, so a flooded channel cannot
+/// starve the others: a message on a quiet control channel waits at most one
+/// round behind bulk data.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum Poll {
+  /// Receive from each endpoint until it is empty before moving to the next.
+  #[default]
+  Drain,
+  /// Receive one message from each endpoint per round until all are empty.
+  RoundRobin,
+  /// As `RoundRobin`, but receive at most `messages` per tick. The next tick
+  /// resumes with the endpoint following the last one received from.
+  Budget {
+    messages : u32
+  },
+  /// Receive up to the given number of messages from each endpoint per round
+  /// until all are empty, with one weight per endpoint in the order the
+  /// endpoints are defined.
+  Weighted {
+    weights : Vec <u32>
+  }
+}
+
+/// When a supervised process is restarted.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Restart {
+  /// The process is not supervised.
+  #[default]
+  Never,
+  /// Restart after a panic.
+  OnPanic,
+  /// Restart after a panic or after the process ends normally.
+  Always
+}
+
+/// Which processes are restarted along with a supervised process.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Strategy {
+  /// Restart only the process itself.
+  #[default]
+  OneForOne,
+  /// Restart every running supervised process of the session.
+  OneForAll
+}
+
+/// How a process run ended, as reported to the session.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Outcome <GPRES> {
+  /// The process ended normally with the given result.
+  Ended (GPRES),
+  /// The process panicked with the given message and its result was lost.
+  Panicked (String),
+  /// The process was aborted by `Session::abort` and has no result.
+  Aborted
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -97,10 +308,19 @@ pub enum ControlFlow {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum KindError {
   AsynchronousZeroMessagesPerUpdate,
-  IsochronousZeroTickMs,
+  AsynchronousZeroIdleTimeout,
+  IsochronousZeroTick,
   IsochronousZeroTicksPerUpdate,
-  MesochronousZeroTickMs,
-  MesochronousZeroTicksPerUpdate
+  MesochronousZeroTick,
+  MesochronousZeroTicksPerUpdate,
+  AnisochronousZeroBackoff,
+  AnisochronousBackoffMinExceedsMax
+}
+
+/// Error in `Process::set_interval`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TimerError {
+  ZeroPeriod
 }
 
 /// Error in `Def`.
@@ -110,7 +330,13 @@ pub enum DefineError {
   DuplicateEndpoint,
   SourcepointEqEndpoint,
   AsynchronousZeroEndpoints,
-  AsynchronousMultipleEndpoints
+  AnisochronousParkZeroEndpoints,
+  AsynchronousPoll,
+  PollZeroBudget,
+  PollZeroWeight,
+  PollWeightsEndpointsMismatch,
+  SupervisionZeroRestarts,
+  SupervisionZeroPeriod
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -147,6 +373,25 @@ pub trait Process <CTX, RES> where
   fn initialize (&mut self) { }
   /// Does nothing by default, may be overridden.
   fn terminate  (&mut self) { }
+  /// Timing information for the current update; only meaningful during a
+  /// call to `update`.
+  #[inline]
+  fn update_context (&self) -> &UpdateContext where CTX : 'static {
+    &self.inner_ref().extended_state().update_context
+  }
+
+  /// Called when an asynchronous process with an idle timeout receives no
+  /// message in time. The timeout counts as an update, so `update_context`
+  /// is refreshed beforehand. Calls `update` by default, may be overridden.
+  fn handle_timeout (&mut self) -> ControlFlow {
+    self.update()
+  }
+  /// Called when a timer set with `set_timer` or `set_interval` expires.
+  /// Returning `ControlFlow::Break` ends the process. Does nothing by
+  /// default, may be overridden.
+  fn handle_timer (&mut self, _timer : TimerId) -> ControlFlow {
+    ControlFlow::Continue
+  }
 
   //
   //  provided
@@ -171,6 +416,123 @@ pub trait Process <CTX, RES> where
     &self.inner_ref().extended_state().def
   }
 
+  /// Set a timer that expires once after the given delay.
+  ///
+  /// Timers are checked by the run loop of every process kind: polling
+  /// processes check once per loop and wake early from sleeping for a tick,
+  /// and a blocked asynchronous process wakes at the earliest deadline.
+  ///
+  /// A timer with a delay too large to be represented by the clock never
+  /// expires.
+  fn set_timer (&mut self, delay : time::Duration) -> TimerId
+    where CTX : 'static
+  {
+    let deadline = self.clock().now().checked_add (delay);
+    self.inner_mut().extended_state_mut().timers.insert (deadline, None)
+  }
+
+  /// Set a timer that expires repeatedly with the given period, starting one
+  /// period from now.
+  ///
+  /// As with `set_timer`, a timer with a period too large to be represented
+  /// by the clock never expires.
+  ///
+  /// # Errors
+  ///
+  /// `TimerError::ZeroPeriod` if the period is zero.
+  fn set_interval (&mut self, period : time::Duration)
+    -> Result <TimerId, TimerError>
+    where CTX : 'static
+  {
+    if period.is_zero() {
+      return Err (TimerError::ZeroPeriod)
+    }
+    let deadline = self.clock().now().checked_add (period);
+    Ok (self.inner_mut().extended_state_mut().timers
+      .insert (deadline, Some (period)))
+  }
+
+  /// Cancel a pending timer. Returns false if the timer already expired or
+  /// was cancelled.
+  fn cancel_timer (&mut self, timer : TimerId) -> bool {
+    self.inner_mut().extended_state_mut().timers.cancel (timer)
+  }
+
+  #[inline]
+  fn timers (&self) -> &Timers where CTX : 'static {
+    &self.inner_ref().extended_state().timers
+  }
+
+  /// The clock of the session that started this process.
+  #[inline]
+  fn clock (&self) -> &std::sync::Arc <dyn Clock> where CTX : 'static {
+    &self.inner_ref().extended_state().clock
+  }
+
+  /// The supervisor of the session that started this process.
+  #[inline]
+  fn supervisor (&self) -> &std::sync::Arc <Supervisor> where CTX : 'static {
+    &self.inner_ref().extended_state().supervisor
+  }
+
+  /// Pending control request made by the session for this process.
+  #[inline]
+  fn control (&self) -> Option <supervisor::Control> where CTX : 'static {
+    self.supervisor().control (self.id().clone().into())
+  }
+
+  /// True when the process has been aborted or the session is shutting down,
+  /// or when the process is supervised and another process of the session
+  /// has started a one-for-all restart since this process was started. Run
+  /// loops end when a process is interrupted.
+  fn interrupted (&self) -> bool where CTX : 'static {
+    self.supervisor().is_shut_down()
+      || self.control() == Some (supervisor::Control::Abort)
+      || self.def().is_supervised()
+        && self.inner_ref().extended_state().generation
+          != self.supervisor().generation()
+  }
+
+  /// Called by run loops at the start of each iteration: if a pause has been
+  /// requested, the process is `Paused` until it is resumed or aborted.
+  /// Returns the time spent paused, by which pending timers are delayed.
+  fn handle_pause (&mut self) -> Option <time::Duration> where CTX : 'static {
+    if self.control() != Some (supervisor::Control::Pause) {
+      return None
+    }
+    self.inner_mut().handle_event (inner::EventParams::Pause{}.into()).unwrap();
+    log::info!(process:?=self.id(); "process paused");
+    let t_pause = self.clock().now();
+    let control = self.supervisor().wait_resumed (self.id().clone().into());
+    let paused  = self.clock().now().duration_since (t_pause);
+    // an aborted process remains paused until the run loop ends
+    if control != Some (supervisor::Control::Abort) {
+      self.inner_mut().handle_event (inner::EventParams::Resume{}.into())
+        .unwrap();
+      self.inner_mut().extended_state_mut().timers.delay (paused);
+      log::info!(process:?=self.id(), paused:?; "process resumed");
+    }
+    Some (paused)
+  }
+
+  /// Call `handle_timer` for each timer that has expired. A
+  /// `ControlFlow::Break` ends the process.
+  fn handle_timers (&mut self) where CTX : 'static {
+    let now = self.clock().now();
+    while self.state_id() == inner::StateId::Running {
+      let Some (timer) = self.inner_mut().extended_state_mut().timers
+        .expire (now) else { break };
+      log::trace!(process:?=self.id(), timer:?; "process timer");
+      match self.handle_timer (timer) {
+        ControlFlow::Continue => {}
+        ControlFlow::Break    => {
+          self.inner_mut().handle_event (inner::EventParams::End{}.into())
+            .unwrap()
+        }
+      }
+    }
+  }
+
   #[inline]
   fn sourcepoints (&self) -> &VecMap <Box <dyn channel::Sourcepoint <CTX>>> {
     &self.inner_ref().extended_state().sourcepoints
@@ -233,6 +595,15 @@ pub trait Process <CTX, RES> where
       = Some (endpoints);
   }
 
+  /// Send on a channel with a single recipient per message.
+  ///
+  /// The message must be the local message type of the channel, otherwise
+  /// the send will panic; use `send_typed` to check this at compile time.
+  ///
+  /// # Errors
+  ///
+  /// `SendError::WrongAddressing` if the channel is a `Source`, which
+  /// requires `send_to`.
   fn send <M : Message <CTX>> (&self, channel_id : CTX::CID, message : M)
     -> Result <(), channel::SendError <CTX::GMSG>>
   where CTX : 'static {
@@ -241,12 +612,26 @@ pub trait Process <CTX, RES> where
       process:?=self.id(), channel:?=channel_id, message=message_name.as_str();
       "process sending message");
     let cid : usize = channel_id.clone().into();
-    self.sourcepoints()[cid].send (message.into()).inspect_err (|_|
-      log::warn!(
-        process:?=self.id(), channel:?=channel_id, message=message_name.as_str();
-        "process send error: receiver disconnected"))
+    self.sourcepoints()[cid].send (message.into()).inspect_err (|err|
+      match *err {
+        channel::SendError::Disconnected (_) => log::warn!(
+          process:?=self.id(), channel:?=channel_id, message=message_name.as_str();
+          "process send error: receiver disconnected"),
+        channel::SendError::Protocol (_) => log::warn!(
+          process:?=self.id(), channel:?=channel_id, message=message_name.as_str();
+          "process send error: protocol violation"),
+        channel::SendError::WrongAddressing (_) => log::warn!(
+          process:?=self.id(), channel:?=channel_id, message=message_name.as_str();
+          "process send error: wrong addressing")
+      })
   }
 
+  /// Send to a specific consumer of a `Source` or `Broadcast` channel.
+  ///
+  /// # Errors
+  ///
+  /// `SendError::WrongAddressing` if the channel does not address individual
+  /// consumers or if the recipient is not a consumer of the channel.
   fn send_to <M : Message <CTX>> (
     &self, channel_id : CTX::CID, recipient : CTX::PID, message : M
   ) -> Result <(), channel::SendError <CTX::GMSG>>
@@ -260,30 +645,213 @@ pub trait Process <CTX, RES> where
       message=message_name.as_str();
       "process sending message to peer");
     let cid : usize = channel_id.clone().into();
-    self.sourcepoints()[cid].send_to (message.into(), recipient.clone()).inspect_err (
-      |_| log::warn!(
-        process:?=self.id(),
-        channel:?=channel_id,
-        peer:?=recipient,
-        message=message_name.as_str();
-        "process send to peer error: receiver disconnected"))
+    self.sourcepoints()[cid].send_to (message.into(), recipient.clone())
+      .inspect_err (|err| match *err {
+        channel::SendError::Disconnected (_) => log::warn!(
+          process:?=self.id(),
+          channel:?=channel_id,
+          peer:?=recipient,
+          message=message_name.as_str();
+          "process send to peer error: receiver disconnected"),
+        channel::SendError::Protocol (_) => log::warn!(
+          process:?=self.id(),
+          channel:?=channel_id,
+          peer:?=recipient,
+          message=message_name.as_str();
+          "process send to peer error: protocol violation"),
+        channel::SendError::WrongAddressing (_) => log::warn!(
+          process:?=self.id(),
+          channel:?=channel_id,
+          peer:?=recipient,
+          message=message_name.as_str();
+          "process send to peer error: wrong addressing")
+      })
+  }
+
+  /// Send without blocking. On a bounded channel that is at capacity the
+  /// message is returned in `TrySendError::Full` so that the caller may drop
+  /// it or retry later.
+  fn try_send <M : Message <CTX>> (&self, channel_id : CTX::CID, message : M)
+    -> Result <(), channel::TrySendError <CTX::GMSG>>
+  where CTX : 'static {
+    let message_name = message.name();
+    log::debug!(
+      process:?=self.id(), channel:?=channel_id, message=message_name.as_str();
+      "process trying to send message");
+    let cid : usize = channel_id.clone().into();
+    self.sourcepoints()[cid].try_send (message.into()).inspect_err (|err|
+      match *err {
+        channel::TrySendError::Full (_) => log::debug!(
+          process:?=self.id(), channel:?=channel_id, message=message_name.as_str();
+          "process try send failed: channel full"),
+        channel::TrySendError::Disconnected (_) => log::warn!(
+          process:?=self.id(), channel:?=channel_id, message=message_name.as_str();
+          "process try send error: receiver disconnected"),
+        channel::TrySendError::Protocol (_) => log::warn!(
+          process:?=self.id(), channel:?=channel_id, message=message_name.as_str();
+          "process try send error: protocol violation"),
+        channel::TrySendError::WrongAddressing (_) => log::warn!(
+          process:?=self.id(), channel:?=channel_id, message=message_name.as_str();
+          "process try send error: wrong addressing")
+      })
+  }
+
+  /// Send to a recipient without blocking. See `try_send`.
+  fn try_send_to <M : Message <CTX>> (
+    &self, channel_id : CTX::CID, recipient : CTX::PID, message : M
+  ) -> Result <(), channel::TrySendError <CTX::GMSG>>
+    where CTX : 'static
+  {
+    let message_name = message.name();
+    log::debug!(
+      process:?=self.id(),
+      channel:?=channel_id,
+      peer:?=recipient,
+      message=message_name.as_str();
+      "process trying to send message to peer");
+    let cid : usize = channel_id.clone().into();
+    self.sourcepoints()[cid].try_send_to (message.into(), recipient.clone())
+      .inspect_err (|err| match *err {
+        channel::TrySendError::Full (_) => log::debug!(
+          process:?=self.id(),
+          channel:?=channel_id,
+          peer:?=recipient,
+          message=message_name.as_str();
+          "process try send to peer failed: channel full"),
+        channel::TrySendError::Disconnected (_) => log::warn!(
+          process:?=self.id(),
+          channel:?=channel_id,
+          peer:?=recipient,
+          message=message_name.as_str();
+          "process try send to peer error: receiver disconnected"),
+        channel::TrySendError::Protocol (_) => log::warn!(
+          process:?=self.id(),
+          channel:?=channel_id,
+          peer:?=recipient,
+          message=message_name.as_str();
+          "process try send to peer error: protocol violation"),
+        channel::TrySendError::WrongAddressing (_) => log::warn!(
+          process:?=self.id(),
+          channel:?=channel_id,
+          peer:?=recipient,
+          message=message_name.as_str();
+          "process try send to peer error: wrong addressing")
+      })
+  }
+
+  /// Send on a channel given by its typed marker from the session `channels`
+  /// module. Unlike `send`, a message that is not the local message type of
+  /// the channel is a compile error rather than a panic.
+  fn send_typed <C : channel::TypedId <CTX>> (&self,
+    _channel : C, message : C::Message
+  ) -> Result <(), channel::SendError <CTX::GMSG>>
+    where CTX : 'static
+  {
+    self.send (C::ID, message)
+  }
+
+  /// Typed version of `send_to`. See `send_typed`.
+  fn send_to_typed <C : channel::TypedId <CTX>> (&self,
+    _channel : C, recipient : CTX::PID, message : C::Message
+  ) -> Result <(), channel::SendError <CTX::GMSG>>
+    where CTX : 'static
+  {
+    self.send_to (C::ID, recipient, message)
+  }
+
+  /// Typed version of `try_send`. See `send_typed`.
+  fn try_send_typed <C : channel::TypedId <CTX>> (&self,
+    _channel : C, message : C::Message
+  ) -> Result <(), channel::TrySendError <CTX::GMSG>>
+    where CTX : 'static
+  {
+    self.try_send (C::ID, message)
+  }
+
+  /// Typed version of `try_send_to`. See `send_typed`.
+  fn try_send_to_typed <C : channel::TypedId <CTX>> (&self,
+    _channel : C, recipient : CTX::PID, message : C::Message
+  ) -> Result <(), channel::TrySendError <CTX::GMSG>>
+    where CTX : 'static
+  {
+    self.try_send_to (C::ID, recipient, message)
   }
 
   /// Run a process to completion and send the result on the result channel.
+  ///
+  /// A panic in any process method is caught. A supervised process is then
+  /// restarted according to its `Supervision`; otherwise the panic is sent to
+  /// the session as `Outcome::Panicked`, the channels of the process are
+  /// dropped, `terminate` is not called, and the session is shut down so that
+  /// the other processes end. An aborted process is shut down the same way
+  /// and reported as `Outcome::Aborted`, without shutting down the session.
   #[inline]
   fn run (&mut self) where
     Self : Sized + 'static,
     CTX  : 'static
   {
-    use message::Global;
     debug_assert_eq!(self.state_id(), inner::StateId::Ready);
-    self.initialize();
-    match *self.kind() {
-      Kind::Asynchronous  {..} => self.run_asynchronous(),
-      Kind::Isochronous   {..} => self.run_isochronous(),
-      Kind::Mesochronous  {..} => self.run_mesochronous(),
-      Kind::Anisochronous      => self.run_anisochronous()
+    let registration = ClockRegistration (
+      self.def().is_clocked().then (|| self.clock().clone()));
+    let outcome = loop {
+      // endpoints are held outside of the unwind boundary so that they can be
+      // returned to a process that panics in its run loop
+      let mut endpoints = None;
+      let run = std::panic::catch_unwind (std::panic::AssertUnwindSafe (|| {
+        self.inner_mut().extended_state_mut().generation =
+          self.supervisor().generation();
+        self.initialize();
+        let endpoints = &*endpoints.insert (self.take_endpoints());
+        match *self.kind() {
+          Kind::Asynchronous  {..} => self.run_asynchronous  (endpoints),
+          Kind::Isochronous   {..} => self.run_isochronous   (endpoints),
+          Kind::Mesochronous  {..} => self.run_mesochronous  (endpoints),
+          Kind::Anisochronous {..} => self.run_anisochronous (endpoints)
+        }
+      }));
+      if let Some (endpoints) = endpoints {
+        self.put_endpoints (endpoints);
+      }
+      let panic = run.err().map (|payload| panicked (self.id(), &*payload));
+      if self.control() == Some (supervisor::Control::Abort) {
+        log::info!(process:?=self.id(); "process aborted");
+        self.inner_mut().handle_event (inner::EventParams::Abort{}.into())
+          .unwrap();
+        break Outcome::Aborted
+      }
+      let interrupted =
+        panic.is_none() && self.state_id() == inner::StateId::Running;
+      end (self);
+      if !restart_requested (self, interrupted, panic.is_some()) {
+        break panic.map_or (Outcome::Ended (()), Outcome::Panicked)
+      }
+      if panic.is_none() {
+        // an interrupted process or a process that ended normally terminates
+        // before restarting
+        if let Err (payload) = std::panic::catch_unwind (
+          std::panic::AssertUnwindSafe (|| self.terminate()))
+        {
+          break Outcome::Panicked (panicked (self.id(), &*payload))
+        }
+      }
+      restart (self);
+    };
+    drop (registration);
+    match outcome {
+      Outcome::Ended (())         => self.finish(),
+      Outcome::Panicked (message) =>
+        shut_down (self, Outcome::Panicked (message)),
+      Outcome::Aborted            => shut_down (self, Outcome::Aborted)
     }
+  }
+
+  /// Terminate a process whose run loop has ended and send the result on the
+  /// result channel.
+  fn finish (&mut self) where
+    Self : Sized + 'static,
+    CTX  : 'static
+  {
+    use message::Global;
     debug_assert_eq!(self.state_id(), inner::StateId::Ended);
     self.terminate();
     // at this point no further messages will be sent or processed so
@@ -315,7 +883,7 @@ pub trait Process <CTX, RES> where
     debug_assert!(self.endpoints().is_none());
     let gpresult = self.global_result();
     let session_handle = &self.inner_ref().as_ref().session_handle;
-    session_handle.result_tx.send (gpresult).unwrap();
+    session_handle.result_tx.send (Outcome::Ended (gpresult)).unwrap();
   }
 
   /// Run a process to completion, send the result to the session, and proceed
@@ -333,53 +901,88 @@ pub trait Process <CTX, RES> where
     continuation (self.into())
   }
 
-  /// Asynchronous run loop waits for messages on the single endpoint held by
-  /// this process and calls the process update method for every $n >= 1$
-  /// messages as specified by the process kind.
-  fn run_asynchronous (&mut self) where
+  /// Asynchronous run loop waits for messages on the endpoints held by this
+  /// process and calls the process update method for every $n >= 1$ messages
+  /// as specified by the process kind.
+  ///
+  /// The process blocks until a message is available on any endpoint,
+  /// receiving from each endpoint in turn. Returning
+  /// `ControlFlow::Break` from `handle_message` closes the channel the message
+  /// was received on, and the loop ends when all channels are closed.
+  fn run_asynchronous (&mut self,
+    endpoints : &VecMap <Box <dyn channel::Endpoint <CTX>>>
+  ) where
     Self : Sized,
     CTX  : 'static
   {
     use message::Global;
 
     self.inner_mut().handle_event (inner::EventParams::Run{}.into()).unwrap();
+    let clock = self.clock().clone();
 
-    let messages_per_update = {
+    let (messages_per_update, idle_timeout) = {
       match *self.kind() {
-        Kind::Asynchronous { messages_per_update } => messages_per_update,
+        Kind::Asynchronous { messages_per_update, idle_timeout } =>
+          (messages_per_update, idle_timeout),
         _ => unreachable!(
           "run asynchronous: process kind does not match run function")
       }
     };
-    let _t_start = time::Instant::now();
+    let t_start = clock.now();
     log::debug!(process:?=self.id(), kind="asynchronous", messages_per_update;
       "process start");
     debug_assert!(1 <= messages_per_update);
     let mut _message_count        = 0;
     let mut update_count          = 0;
     let mut messages_since_update = 0;
+    let mut t_last_update         = t_start;
 
-    let endpoints = self.take_endpoints();
-    { // create a scope here so the endpoints can be returned after this borrow
-    let (cid, endpoint) = endpoints.iter().next().unwrap();
-    #[expect(clippy::cast_possible_truncation)]
-    // NOTE: unwrap requires that err is debug
-    let Ok (channel_id) = CTX::CID::try_from (cid as channel::IdReprType)
-      else { unreachable!() };
-    '_run_loop: while self.state_id() == inner::StateId::Running {
-      // wait on message
-      match endpoint.recv() {
-        Ok (message) => {
+    let endpoints : Vec <(CTX::CID, &dyn channel::Endpoint <CTX>)> =
+      endpoints.iter().map (|(cid, endpoint)| {
+        #[expect(clippy::cast_possible_truncation)]
+        // NOTE: unwrap requires that err is debug
+        let Ok (channel_id) = CTX::CID::try_from (cid as channel::IdReprType)
+          else { unreachable!() };
+        (channel_id, endpoint.as_ref())
+      }).collect();
+    let notifier = endpoints.iter()
+      .find_map (|(_, endpoint)| endpoint.notifier())
+      .expect ("asynchronous process endpoints have a notifier");
+    let mut open_channels = vec![true; endpoints.len()];
+    let mut next_channel  = 0;
+    let next_idle_deadline =
+      || idle_timeout.and_then (|timeout| clock.now().checked_add (timeout));
+    let mut idle_deadline = next_idle_deadline();
+    '_run_loop: while self.state_id() == inner::StateId::Running
+      && !self.interrupted()
+    {
+      if self.handle_pause().is_some() {
+        idle_deadline = next_idle_deadline();
+        continue
+      }
+      // wait on message until the idle timeout or the next timer
+      let deadline = idle_deadline.into_iter()
+        .chain (self.timers().next_deadline()).min()
+        .map (|deadline| clock.system_deadline (deadline));
+      let received = select_message (self.id(), &endpoints,
+        &mut open_channels, &mut next_channel, notifier, deadline,
+        &|| self.interrupted() || self.control().is_some());
+      match received {
+        Ok ((channel_index, message)) => {
+          idle_deadline = next_idle_deadline();
           log::debug!(
             process:?=self.id(),
-            channel:?=channel_id,
+            channel:?=endpoints[channel_index].0,
             message=message.inner_name().as_str();
             "process received message");
           let handle_message_result = self.handle_message (message);
           match handle_message_result {
             ControlFlow::Continue => {}
             ControlFlow::Break    => {
-              if self.state_id() == inner::StateId::Running {
+              open_channels[channel_index] = false;
+              if !open_channels.contains (&true)
+                && self.state_id() == inner::StateId::Running
+              {
                 self.inner_mut().handle_event (inner::EventParams::End{}.into())
                   .unwrap();
               }
@@ -388,9 +991,36 @@ pub trait Process <CTX, RES> where
           _message_count         += 1;
           messages_since_update += 1;
         }
-        Err (channel::RecvError) => {
-          log::info!(process:?=self.id(), channel:?=channel_id;
-            "process receive failed: sender disconnected");
+        Err (channel::RecvTimeoutError::Timeout) => if idle_deadline
+          .is_some_and (|deadline| deadline <= clock.now())
+        {
+          log::trace!(process:?=self.id(); "process idle timeout");
+          // the timeout counts as an update of the process
+          let t_update = clock.now();
+          self.inner_mut().extended_state_mut().update_context =
+            UpdateContext {
+              update:       update_count,
+              tick:         update_count,
+              dt:           t_update.duration_since (t_last_update),
+              lateness:     time::Duration::ZERO,
+              messages:     messages_since_update.into(),
+              missed_ticks: 0
+            };
+          t_last_update = t_update;
+          match self.handle_timeout() {
+            ControlFlow::Continue => {}
+            ControlFlow::Break    => {
+              if self.state_id() == inner::StateId::Running {
+                self.inner_mut().handle_event (inner::EventParams::End{}.into())
+                  .unwrap();
+              }
+            }
+          }
+          update_count += 1;
+          messages_since_update = 0;
+          idle_deadline = next_idle_deadline();
+        }
+        Err (channel::RecvTimeoutError::Disconnected) => {
           if self.state_id() == inner::StateId::Running {
             self.inner_mut().handle_event (inner::EventParams::End{}.into())
               .unwrap();
@@ -401,6 +1031,16 @@ pub trait Process <CTX, RES> where
         // update
         log::trace!(process:?=self.id(), update=update_count;
           "process update");
+        let t_update = clock.now();
+        self.inner_mut().extended_state_mut().update_context = UpdateContext {
+          update:       update_count,
+          tick:         update_count,
+          dt:           t_update.duration_since (t_last_update),
+          lateness:     time::Duration::ZERO,
+          messages:     messages_since_update.into(),
+          missed_ticks: 0
+        };
+        t_last_update = t_update;
         let update_result = self.update();
         match update_result {
           ControlFlow::Continue => {}
@@ -414,64 +1054,72 @@ pub trait Process <CTX, RES> where
         update_count += 1;
         messages_since_update = 0;
       }
+      self.handle_timers();
     } // end 'run_loop
-    } // end borrow endpoint
-    self.put_endpoints (endpoints);
   } // end fn run_asynchronous
 
   /// This function implements a fixed-timestep update loop.
   ///
-  /// Time is checked immediately after update and the thread is put to sleep
-  /// for the time remaining until the next update, plus 1 ms since the thread
-  /// usually wakes up slightly before the set time. In practice this means
-  /// that the update time lags behind the target time by about 1ms or so, but
-  /// the time between updates is consistent. If the thread does somehow wake
-  /// up too early, then no update will be done and the thread will sleep or
-  /// else loop immediately depending on the result of a second time query.
+  /// Time is checked immediately after update and the thread waits until the
+  /// next tick using the `Sleep` strategy of the process kind. If the thread
+  /// does somehow wake up too early, then no update will be done and the
+  /// thread will sleep or else loop immediately depending on the result of a
+  /// second time query.
   ///
   /// After an update, if the next (absolute) tick time has already passed,
   /// then the thread will not sleep and instead will loop immediately. Note
   /// that the tick time is measured on an absolute clock, allowing the thread
   /// to "catch up" in case of a long update by processing the "backlog" of
   /// ticks as fast as possible.
-  fn run_isochronous (&mut self) where
+  fn run_isochronous (&mut self,
+    endpoints : &VecMap <Box <dyn channel::Endpoint <CTX>>>
+  ) where
     Self : Sized,
     CTX  : 'static
   {
     self.inner_mut().handle_event (inner::EventParams::Run{}.into()).unwrap();
+    let clock = self.clock().clone();
 
-    let t_start = time::Instant::now();
-    let (tick_ms, ticks_per_update) = {
+    let t_start = clock.now();
+    let (tick_dur, ticks_per_update, sleep, catch_up) = {
       match *self.kind() {
-        Kind::Isochronous { tick_ms, ticks_per_update }
-          => (tick_ms, ticks_per_update),
+        Kind::Isochronous { tick, ticks_per_update, sleep, catch_up }
+          => (tick, ticks_per_update, sleep, catch_up),
         _ => unreachable!(
           "run synchronous: process kind does not match run function")
       }
     };
     log::debug!(
-      process:?=self.id(), kind="isochronous", tick_ms, ticks_per_update;
+      process:?=self.id(), kind="isochronous", tick_ns=tick_dur.as_nanos(),
+      ticks_per_update, sleep:?, catch_up:?;
       "process start");
-    debug_assert!(1 <= tick_ms);
+    debug_assert!(!tick_dur.is_zero());
     debug_assert!(1 <= ticks_per_update);
-    let tick_dur = time::Duration::from_millis (tick_ms as u64);
     let mut t_last             = t_start - tick_dur;
     let mut t_next             = t_start;
     let mut ticks_since_update = 0;
     let mut tick_count         = 0;
     let mut message_count      = 0;
     let mut update_count       = 0;
+    let mut missed_ticks       = 0;
+    let mut t_last_update      = t_start;
 
-    let endpoints              = self.take_endpoints();
+    let mut poller             = Poller::new (self.def(), endpoints);
     let mut num_open_channels  = endpoints.len();
     let mut open_channels      = smallvec::SmallVec::<[bool; 8]>::from_vec ({
       let mut v = Vec::with_capacity (num_open_channels);
       v.resize (num_open_channels, true);
       v
     });
-    '_run_loop: while self.state_id() == inner::StateId::Running {
-      let t_now = time::Instant::now();
-      if t_next < t_now {
+    '_run_loop: while self.state_id() == inner::StateId::Running
+      && !self.interrupted()
+    {
+      if let Some (paused) = self.handle_pause() {
+        t_next += paused;
+        continue
+      }
+      let t_now = clock.now();
+      if t_next <= t_now {
         log::trace!(
           process:?=self.id(),
           tick=tick_count,
@@ -481,8 +1129,9 @@ pub trait Process <CTX, RES> where
         t_next += tick_dur;
 
         // poll messages
-        poll_messages (self,
-          &endpoints, &mut open_channels, &mut num_open_channels, &mut message_count);
+        let tick_message_count = message_count;
+        poll_messages (self, endpoints, &mut poller,
+          &mut open_channels, &mut num_open_channels, &mut message_count);
 
         tick_count += 1;
         ticks_since_update += 1;
@@ -490,6 +1139,17 @@ pub trait Process <CTX, RES> where
         if ticks_since_update == ticks_per_update {
           log::trace!(process:?=self.id(), update=update_count;
             "process update");
+          let t_update = clock.now();
+          self.inner_mut().extended_state_mut().update_context = UpdateContext {
+            update:       update_count,
+            tick:         tick_count - 1,
+            dt:           t_update.duration_since (t_last_update),
+            lateness:     t_now.duration_since (t_last),
+            messages:     (message_count - tick_message_count) as u64,
+            missed_ticks
+          };
+          t_last_update = t_update;
+          missed_ticks  = 0;
           let update_result = self.update();
           match update_result {
             ControlFlow::Continue => {}
@@ -503,95 +1163,118 @@ pub trait Process <CTX, RES> where
           update_count += 1;
           ticks_since_update = 0;
         }
-      } else {
+      } else if self.timers().next_deadline()
+        .is_none_or (|deadline| t_now < deadline)
+      {
         log::warn!(
           process:?=self.id(),
           tick=tick_count,
           until_ns=t_next.duration_since (t_now).as_nanos();
           "process tick too early");
       }
+      self.handle_timers();
 
-      let t_after = time::Instant::now();
+      if self.state_id() != inner::StateId::Running {
+        // no wait after the final update
+        break
+      }
+      let t_after = clock.now();
       if t_after < t_next {
-        // must be positive
-        let t_until = t_next.duration_since (t_after);
-        std::thread::sleep (time::Duration::from_millis (
-          1 +  // add 1ms to avoid too-early update
-          t_until.as_secs()*1000 +
-          t_until.subsec_nanos() as u64/1_000_000))
+        // wake early for a timer
+        clock.sleep_until (self.timers().next_deadline()
+          .map_or (t_next, |deadline| deadline.min (t_next)), sleep);
       } else {
         log::warn!(
           process:?=self.id(),
           tick=tick_count,
           after_ns=t_after.duration_since (t_next).as_nanos();
           "process late tick");
+        let skip = catch_up.skip (t_after.duration_since (t_next), tick_dur);
+        if 0 < skip {
+          log::warn!(process:?=self.id(), tick=tick_count, skipped=skip;
+            "process skipped late ticks");
+          t_last += tick_dur * skip;
+          t_next += tick_dur * skip;
+          if catch_up == CatchUp::Report {
+            missed_ticks += u64::from (skip);
+          }
+        }
       }
 
     } // end 'run_loop
-    self.put_endpoints (endpoints);
   } // end fn run_isochronous
 
   /// This function implements a rate-limited update loop.
   ///
-  /// Time is checked immediately after update and the thread is put to sleep
-  /// for the time remaining until the next update, plus 1 ms since the thread
-  /// usually wakes up slightly before the set time. In practice this means
-  /// that the update time lags behind the target time by about 1ms or so, but
-  /// the time between updates is consistent. If the thread does somehow wake
-  /// up too early, then no update will be done and the thread will sleep, or
-  /// else loop immediately depending on the result of a second time query.
+  /// Time is checked immediately after update and the thread waits until the
+  /// next tick using the `Sleep` strategy of the process kind. If the thread
+  /// does somehow wake up too early, then no update will be done and the
+  /// thread will sleep, or else loop immediately depending on the result of a
+  /// second time query.
   ///
   /// After a tick, if the next tick time has already passed, then the thread
   /// will not sleep and instead will loop immediately.
-  fn run_mesochronous (&mut self) where
+  fn run_mesochronous (&mut self,
+    endpoints : &VecMap <Box <dyn channel::Endpoint <CTX>>>
+  ) where
     Self : Sized,
     CTX  : 'static
   {
     self.inner_mut().handle_event (inner::EventParams::Run{}.into()).unwrap();
+    let clock = self.clock().clone();
 
-    let t_start = time::Instant::now();
-    let (tick_ms, ticks_per_update) = {
+    let t_start = clock.now();
+    let (tick_dur, ticks_per_update, sleep) = {
       match *self.kind() {
-        Kind::Mesochronous { tick_ms, ticks_per_update }
-          => (tick_ms, ticks_per_update),
+        Kind::Mesochronous { tick, ticks_per_update, sleep }
+          => (tick, ticks_per_update, sleep),
         _ => unreachable!(
           "run synchronous: process kind does not match run function")
       }
     };
     log::debug!(
-      process:?=self.id(), kind="mesochronous", tick_ms, ticks_per_update;
+      process:?=self.id(), kind="mesochronous", tick_ns=tick_dur.as_nanos(),
+      ticks_per_update, sleep:?;
       "process start");
-    debug_assert!(1 <= tick_ms);
+    debug_assert!(!tick_dur.is_zero());
     debug_assert!(1 <= ticks_per_update);
-    let tick_dur = time::Duration::from_millis (tick_ms as u64);
     let mut _t_last            = t_start - tick_dur;
     let mut t_next             = t_start;
     let mut ticks_since_update = 0;
     let mut tick_count         = 0;
     let mut message_count      = 0;
     let mut update_count       = 0;
+    let mut t_last_update      = t_start;
 
-    let endpoints              = self.take_endpoints();
+    let mut poller             = Poller::new (self.def(), endpoints);
     let mut num_open_channels  = endpoints.len();
     let mut open_channels      = smallvec::SmallVec::<[bool; 8]>::from_vec ({
       let mut v = Vec::with_capacity (num_open_channels);
       v.resize (num_open_channels, true);
       v
     });
-    '_run_loop: while self.state_id() == inner::StateId::Running {
-      let t_now = time::Instant::now();
-      if t_next < t_now {
+    '_run_loop: while self.state_id() == inner::StateId::Running
+      && !self.interrupted()
+    {
+      if let Some (paused) = self.handle_pause() {
+        t_next += paused;
+        continue
+      }
+      let t_now = clock.now();
+      if t_next <= t_now {
         log::trace!(
           process:?=self.id(),
           tick=tick_count,
           since_ns=t_now.duration_since (t_next).as_nanos();
           "process tick");
+        let lateness = t_now.duration_since (t_next);
         _t_last = t_now;
         t_next  = t_now + tick_dur;
 
         // poll messages
-        poll_messages (self,
-          &endpoints, &mut open_channels, &mut num_open_channels, &mut message_count);
+        let tick_message_count = message_count;
+        poll_messages (self, endpoints, &mut poller,
+          &mut open_channels, &mut num_open_channels, &mut message_count);
 
         tick_count += 1;
         ticks_since_update += 1;
@@ -599,6 +1282,16 @@ pub trait Process <CTX, RES> where
         if ticks_since_update == ticks_per_update {
           log::trace!(process:?=self.id(), update=update_count;
             "process update");
+          let t_update = clock.now();
+          self.inner_mut().extended_state_mut().update_context = UpdateContext {
+            update:       update_count,
+            tick:         tick_count - 1,
+            dt:           t_update.duration_since (t_last_update),
+            lateness,
+            messages:     (message_count - tick_message_count) as u64,
+            missed_ticks: 0
+          };
+          t_last_update = t_update;
           let update_result = self.update();
           match update_result {
             ControlFlow::Continue => {}
@@ -612,22 +1305,26 @@ pub trait Process <CTX, RES> where
           update_count += 1;
           ticks_since_update = 0;
         }
-      } else {
+      } else if self.timers().next_deadline()
+        .is_none_or (|deadline| t_now < deadline)
+      {
         log::warn!(
           process:?=self.id(),
           tick=tick_count,
           until_ns=t_next.duration_since (t_now).as_nanos();
           "process tick too early");
       }
+      self.handle_timers();
 
-      let t_after = time::Instant::now();
+      if self.state_id() != inner::StateId::Running {
+        // no wait after the final update
+        break
+      }
+      let t_after = clock.now();
       if t_after < t_next {
-        // must be positive
-        let t_until = t_next.duration_since (t_after);
-        std::thread::sleep (time::Duration::from_millis (
-          1 +  // add 1ms to avoid too-early update
-          t_until.as_secs()*1000 +
-          t_until.subsec_nanos() as u64/1_000_000))
+        // wake early for a timer
+        clock.sleep_until (self.timers().next_deadline()
+          .map_or (t_next, |deadline| deadline.min (t_next)), sleep);
       } else {
         log::warn!(
           process:?=self.id(),
@@ -637,35 +1334,66 @@ pub trait Process <CTX, RES> where
       }
 
     } // end 'run_loop
-    self.put_endpoints (endpoints);
   } // end fn run_mesochronous
 
   /// An un-timed run loop that polls for messages.
-  fn run_anisochronous (&mut self) where
+  fn run_anisochronous (&mut self,
+    endpoints : &VecMap <Box <dyn channel::Endpoint <CTX>>>
+  ) where
     Self : Sized,
     CTX  : 'static
   {
     self.inner_mut().handle_event (inner::EventParams::Run{}.into()).unwrap();
+    let clock = self.clock().clone();
 
-    let _t_start = time::Instant::now();
-    debug_assert_eq!(Kind::Anisochronous, *self.kind());
-    log::debug!(process:?=self.id(), kind="anisochronous"; "process start");
+    let t_start = clock.now();
+    let Kind::Anisochronous { idle } = *self.kind() else {
+      unreachable!(
+        "run anisochronous: process kind does not match run function")
+    };
+    log::debug!(process:?=self.id(), kind="anisochronous", idle:?;
+      "process start");
     let mut message_count = 0;
     let mut update_count  = 0;
+    let mut t_last_update = t_start;
+    let mut backoff       = None;
 
-    let endpoints = self.take_endpoints();
+    let mut poller            = Poller::new (self.def(), endpoints);
     let mut num_open_channels = endpoints.len();
     let mut open_channels     = smallvec::SmallVec::<[bool; 8]>::from_vec ({
       let mut v = Vec::with_capacity (num_open_channels);
       v.resize (num_open_channels, true);
       v
     });
-    '_run_loop: while self.state_id() == inner::StateId::Running {
+    let notifier = endpoints.values().find_map (|endpoint| endpoint.notifier())
+      .cloned();
+    debug_assert_eq!(notifier.is_some(), idle == Idle::Park);
+    '_run_loop: while self.state_id() == inner::StateId::Running
+      && !self.interrupted()
+    {
+      if self.handle_pause().is_some() {
+        continue
+      }
+      // record the generation before polling so that a message sent during
+      // the poll or update does not leave a parked process waiting
+      let generation = notifier.as_ref()
+        .map (channel::select::Notifier::generation);
       // poll messages
-      poll_messages (self,
-        &endpoints, &mut open_channels, &mut num_open_channels, &mut message_count);
+      let tick_message_count = message_count;
+      poll_messages (self, endpoints, &mut poller,
+        &mut open_channels, &mut num_open_channels, &mut message_count);
       // update
       log::trace!(process:?=self.id(), update=update_count; "process update");
+      let t_update = clock.now();
+      self.inner_mut().extended_state_mut().update_context = UpdateContext {
+        update:       update_count,
+        tick:         update_count,
+        dt:           t_update.duration_since (t_last_update),
+        lateness:     time::Duration::ZERO,
+        messages:     (message_count - tick_message_count) as u64,
+        missed_ticks: 0
+      };
+      t_last_update = t_update;
       let update_result = self.update();
       match update_result {
         ControlFlow::Continue => {}
@@ -677,9 +1405,43 @@ pub trait Process <CTX, RES> where
         }
       }
       update_count += 1;
+      self.handle_timers();
+
+      // idle; sleeping or parking wakes early for a timer
+      if message_count == tick_message_count
+        && self.state_id() == inner::StateId::Running
+      {
+        let until_timer = self.timers().next_deadline().map (|deadline|
+          deadline.saturating_duration_since (clock.now()));
+        match idle {
+          Idle::Spin => {}
+          Idle::Yield => std::thread::yield_now(),
+          Idle::Backoff { min, max } => {
+            let now = clock.now();
+            backoff = next_backoff (backoff, min, max, now);
+            if let Some (sleep) = backoff {
+              let wait = until_timer.map_or (sleep, |until| until.min (sleep));
+              clock.sleep_until (now + wait, Sleep::Thread);
+            } else {
+              std::thread::yield_now();
+            }
+          }
+          // an interrupt after the generation was recorded wakes the process
+          Idle::Park => if !self.interrupted() && self.control().is_none() {
+            log::trace!(process:?=self.id(); "process park");
+            let notifier = notifier.as_ref().unwrap();
+            if let Some (until) = until_timer {
+              notifier.wait_timeout (generation.unwrap(), until);
+            } else {
+              notifier.wait (generation.unwrap());
+            }
+          }
+        }
+      } else {
+        backoff = None;
+      }
 
     } // end 'run_loop
-    self.put_endpoints (endpoints);
   } // end fn run_anisochronous
 
 } // end trait Process
@@ -705,6 +1467,13 @@ pub trait Global <CTX> where
 {
   fn id (&self) -> CTX::PID;
   fn run (&mut self);
+  /// Initialize the process to be advanced in steps on the current thread
+  /// instead of running its own loop (see `step`). Returns `None` if
+  /// initialization panicked.
+  fn start_steps (&mut self) -> Option <step::Stepper <CTX>>;
+  fn step (&mut self, stepper : &mut step::Stepper <CTX>) -> step::Step;
+  /// Finish a process after a step returned `step::Step::Ended`.
+  fn finish_steps (&mut self, stepper : step::Stepper <CTX>);
   //fn run_continue (mut self) -> Option <()>;
 }
 
@@ -790,33 +1559,175 @@ impl <CTX : session::Context> Def <CTX> {
   /// # }
   /// ```
   ///
-  /// Asynchronous process multiple endpoints:
+  pub fn define (
+    id           : CTX::PID,
+    kind         : Kind,
+    sourcepoints : Vec <CTX::CID>,
+    endpoints    : Vec <CTX::CID>
+  ) -> Result <Self, Vec <DefineError>> {
+    Self::define_with_poll (id, kind, sourcepoints, endpoints, Poll::default())
+  }
+
+  /// Define a process that receives messages according to the given polling
+  /// policy. See `define` for validation of sourcepoints and endpoints.
+  ///
+  /// # Errors
+  ///
+  /// A polling policy given for an asynchronous process, a zero budget or
+  /// weight, or a number of weights that does not match the number of
+  /// endpoints.
+  ///
+  /// A policy is usually given with `poll` in `def_session!`. Here each
+  /// round of polling receives up to one command and then up to two samples:
+  ///
+  /// ```
+  /// extern crate apis;
+  ///
+  /// apis::def_session! {
+  ///   context Polling {
+  ///     PROCESSES where
+  ///       let process    = self,
+  ///       let message_in = message_in
+  ///     [
+  ///       process Sender () {
+  ///         kind { apis::process::Kind::anisochronous_default() }
+  ///         sourcepoints [Commands, Samples]
+  ///         endpoints    []
+  ///         handle_message { unreachable!() }
+  ///         update {
+  ///           for _ in 0..2 {
+  ///             process.send (ChannelId::Commands, Command::Stop).unwrap();
+  ///           }
+  ///           for _ in 0..4 {
+  ///             process.send (ChannelId::Samples, Sample::Value).unwrap();
+  ///           }
+  ///           apis::process::ControlFlow::Break
+  ///         }
+  ///       }
+  ///       process Receiver (order : String) -> (String) {
+  ///         kind { apis::process::Kind::isochronous_default() }
+  ///         sourcepoints []
+  ///         endpoints    [Commands, Samples]
+  ///         poll { apis::process::Poll::Weighted { weights: vec![1, 2] } }
+  ///         terminate { process.result = process.order.clone() }
+  ///         handle_message {
+  ///           process.order.push (match message_in {
+  ///             GlobalMessage::Command (_) => 'c',
+  ///             GlobalMessage::Sample  (_) => 's'
+  ///           });
+  ///           apis::process::ControlFlow::Continue
+  ///         }
+  ///         update { apis::process::ControlFlow::Continue }
+  ///       }
+  ///     ]
+  ///     CHANNELS [
+  ///       channel Commands <Command> (Simplex) {
+  ///         producers [Sender]
+  ///         consumers [Receiver]
+  ///       }
+  ///       channel Samples <Sample> (Simplex) {
+  ///         producers [Sender]
+  ///         consumers [Receiver]
+  ///       }
+  ///     ]
+  ///     MESSAGES [
+  ///       message Command { Stop }
+  ///       message Sample  { Value }
+  ///     ]
+  ///   }
+  /// }
+  ///
+  /// fn main() {
+  ///   use apis::session::Context;
+  ///   let mut session : apis::Session <Polling> =
+  ///     Polling::def().unwrap().into();
+  ///   // every message is sent in a single step of the sender
+  ///   let results = session.run_deterministic (0);
+  ///   let GlobalPresult::Receiver (order) =
+  ///     &results[ProcessId::Receiver as usize] else { unreachable!() };
+  ///   assert_eq!(order, "csscss");
+  /// }
+  /// ```
+  pub fn define_with_poll (
+    id           : CTX::PID,
+    kind         : Kind,
+    sourcepoints : Vec <CTX::CID>,
+    endpoints    : Vec <CTX::CID>,
+    poll         : Poll
+  ) -> Result <Self, Vec <DefineError>> {
+    Self::define_with_supervision (
+      id, kind, sourcepoints, endpoints, poll, Supervision::default())
+  }
+
+  /// Define a process that is restarted by its session according to the
+  /// given supervision. See `define_with_poll` for validation of the polling
+  /// policy.
+  ///
+  /// # Errors
+  ///
+  /// Supervised process with a zero restart period:
   ///
   /// ```
   /// # extern crate apis;
-  /// # use apis::{channel,message,process};
-  /// # use apis::session::mock::*;
-  /// # use channel::Id;
+  /// # use apis::process;
+  /// # apis::def_session! {
+  /// #   context Mycontext {
+  /// #     PROCESSES where
+  /// #       let process    = self,
+  /// #       let message_in = message_in
+  /// #     [
+  /// #       process A () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   []
+  /// #         endpoints      [X]
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #       process B () {
+  /// #         kind           { apis::process::Kind::isochronous_default() }
+  /// #         sourcepoints   [X]
+  /// #         endpoints      []
+  /// #         handle_message { apis::process::ControlFlow::Break }
+  /// #         update         { apis::process::ControlFlow::Break }
+  /// #       }
+  /// #     ]
+  /// #     CHANNELS [
+  /// #       channel X <T> (Simplex) {
+  /// #         producers [B]
+  /// #         consumers [A]
+  /// #       }
+  /// #     ]
+  /// #     MESSAGES [
+  /// #       message T {}
+  /// #     ]
+  /// #   }
+  /// # }
   /// # fn main() {
-  /// let result = process::Def::<Mycontext>::define (
+  /// let result = process::Def::<Mycontext>::define_with_supervision (
   ///   ProcessId::A,
-  ///   process::Kind::asynchronous_default(),
-  ///   vec![ChannelId::Z],
-  ///   vec![ChannelId::X, ChannelId::Y]);
+  ///   process::Kind::isochronous_default(),
+  ///   vec![],
+  ///   vec![ChannelId::X],
+  ///   process::Poll::Drain,
+  ///   process::Supervision {
+  ///     restart: process::Restart::OnPanic,
+  ///     period:  std::time::Duration::ZERO,
+  ///     .. process::Supervision::default()
+  ///   });
   /// assert_eq!(
-  ///   result,
-  ///   Err (vec![process::DefineError::AsynchronousMultipleEndpoints]));
+  ///   result, Err (vec![process::DefineError::SupervisionZeroPeriod]));
   /// # }
   /// ```
-  ///
-  pub fn define (
+  pub fn define_with_supervision (
     id           : CTX::PID,
     kind         : Kind,
     sourcepoints : Vec <CTX::CID>,
-    endpoints    : Vec <CTX::CID>
+    endpoints    : Vec <CTX::CID>,
+    poll         : Poll,
+    supervision  : Supervision
   ) -> Result <Self, Vec <DefineError>> {
     let def = Def {
-      id, kind, sourcepoints, endpoints
+      id, kind, sourcepoints, endpoints, poll, supervision
     };
     def.validate_role() ?;
     Ok (def)
@@ -838,6 +1749,40 @@ impl <CTX : session::Context> Def <CTX> {
     &self.endpoints
   }
 
+  pub const fn poll (&self) -> &Poll {
+    &self.poll
+  }
+
+  pub const fn supervision (&self) -> &Supervision {
+    &self.supervision
+  }
+
+  /// True for a process that is restarted by its session.
+  pub fn is_supervised (&self) -> bool {
+    self.supervision.restart != Restart::Never
+  }
+
+  /// True for a process that waits on its `Clock`: a timed process, or an
+  /// anisochronous process that backs off when idle.
+  pub const fn is_clocked (&self) -> bool {
+    matches!(self.kind,
+      Kind::Isochronous {..} | Kind::Mesochronous {..} |
+      Kind::Anisochronous { idle: Idle::Backoff {..} })
+  }
+
+  /// True for a process that blocks on its endpoints through a
+  /// `channel::select::Notifier`: an asynchronous process, or an
+  /// anisochronous process that parks when idle.
+  pub const fn is_notified (&self) -> bool {
+    match self.kind {
+      Kind::Asynchronous  {..} |
+      Kind::Anisochronous { idle: Idle::Park } => true,
+      Kind::Isochronous   {..} |
+      Kind::Mesochronous  {..} |
+      Kind::Anisochronous {..} => false
+    }
+  }
+
   fn validate_role (&self) -> Result <(), Vec <DefineError>> {
     let mut errors = Vec::new();
 
@@ -859,7 +1804,12 @@ impl <CTX : session::Context> Def <CTX> {
       errors.push (DefineError::DuplicateEndpoint);
     }
 
-    // self-loops
+    // self-loops; bidirectional channels are held as both a sourcepoint and
+    // an endpoint
+    let bidirectional =
+      |cid : &CTX::CID| channel::Id::def (cid).kind().is_bidirectional();
+    producers_dedup.retain (|cid| !bidirectional (cid));
+    consumers_dedup.retain (|cid| !bidirectional (cid));
     let mut producers_and_consumers = producers_dedup.clone();
     producers_and_consumers.append (&mut consumers_dedup.clone());
     producers_and_consumers.as_mut_slice().sort();
@@ -877,6 +1827,36 @@ impl <CTX : session::Context> Def <CTX> {
       errors.append (&mut errs);
     }
 
+    // validate polling policy
+    if self.poll != Poll::Drain && matches!(self.kind, Kind::Asynchronous {..}) {
+      // asynchronous processes do not poll
+      errors.push (DefineError::AsynchronousPoll)
+    }
+    match &self.poll {
+      Poll::Drain | Poll::RoundRobin => {}
+      Poll::Budget { messages } => if *messages == 0 {
+        errors.push (DefineError::PollZeroBudget)
+      }
+      Poll::Weighted { weights } => {
+        if weights.len() != self.endpoints.len() {
+          errors.push (DefineError::PollWeightsEndpointsMismatch)
+        }
+        if weights.contains (&0) {
+          errors.push (DefineError::PollZeroWeight)
+        }
+      }
+    }
+
+    // validate supervision
+    if self.is_supervised() {
+      if self.supervision.max_restarts == 0 {
+        errors.push (DefineError::SupervisionZeroRestarts)
+      }
+      if self.supervision.period.is_zero() {
+        errors.push (DefineError::SupervisionZeroPeriod)
+      }
+    }
+
     if !errors.is_empty() {
       Err (errors)
     } else {
@@ -885,48 +1865,152 @@ impl <CTX : session::Context> Def <CTX> {
   }
 }
 
+impl Default for Supervision {
+  /// Not restarted, with a restart intensity of 3 restarts in 5 seconds once
+  /// a restart policy is given.
+  fn default() -> Self {
+    Supervision {
+      restart:      Restart::Never,
+      strategy:     Strategy::OneForOne,
+      max_restarts: 3,
+      period:       time::Duration::from_secs (5)
+    }
+  }
+}
+
+impl Timers {
+  /// Deadline of the earliest pending timer.
+  pub fn next_deadline (&self) -> Option <time::Instant> {
+    self.pending.iter().filter_map (|timer| timer.deadline).min()
+  }
+
+  fn insert (&mut self,
+    deadline : Option <time::Instant>,
+    interval : Option <time::Duration>
+  ) -> TimerId {
+    let id = TimerId (self.next_id);
+    self.next_id += 1;
+    self.pending.push (Timer { id, deadline, interval });
+    id
+  }
+
+  fn cancel (&mut self, id : TimerId) -> bool {
+    let count = self.pending.len();
+    self.pending.retain (|timer| timer.id != id);
+    self.pending.len() < count
+  }
+
+  /// Move every deadline later by the given duration.
+  fn delay (&mut self, duration : time::Duration) {
+    for timer in &mut self.pending {
+      timer.deadline = timer.deadline
+        .and_then (|deadline| deadline.checked_add (duration));
+    }
+  }
+
+  /// Remove the earliest timer that expired at or before `now`, rescheduling
+  /// it if it repeats. A repeating timer that has fallen behind by more than
+  /// one period is rescheduled one period after `now`.
+  fn expire (&mut self, now : time::Instant) -> Option <TimerId> {
+    let (index, deadline) = self.pending.iter().enumerate()
+      .filter_map (|(index, timer)| Some ((index, timer.deadline?)))
+      .filter (|(_, deadline)| *deadline <= now)
+      .min_by_key (|(_, deadline)| *deadline)?;
+    let timer = &mut self.pending[index];
+    let id    = timer.id;
+    if let Some (interval) = timer.interval {
+      timer.deadline = deadline.checked_add (interval)
+        .filter (|next| now < *next)
+        .or_else (|| now.checked_add (interval));
+    } else {
+      self.pending.swap_remove (index);
+    }
+    Some (id)
+  }
+}
+
+impl Drop for ClockRegistration {
+  fn drop (&mut self) {
+    if let Some (clock) = self.0.take() {
+      clock.unregister();
+    }
+  }
+}
+
+impl Poller {
+  fn new <CTX : session::Context> (
+    def       : &Def <CTX>,
+    endpoints : &VecMap <Box <dyn channel::Endpoint <CTX>>>
+  ) -> Self {
+    let quotas = endpoints.keys().map (|cid| match &def.poll {
+      Poll::Drain => None,
+      Poll::RoundRobin | Poll::Budget {..} => Some (1),
+      Poll::Weighted { weights } => {
+        let index = def.endpoints.iter()
+          .position (|endpoint| endpoint.clone().into() == cid).unwrap();
+        Some (weights[index])
+      }
+    }).collect();
+    let budget = match &def.poll {
+      Poll::Budget { messages } => Some (*messages),
+      _ => None
+    };
+    Poller {
+      quotas, budget, next_channel: 0, drain: def.poll == Poll::Drain
+    }
+  }
+}
+
 impl Kind {
   pub fn asynchronous_default() -> Self {
     const MESSAGES_PER_UPDATE : u32 = 1;
-    Kind::new_asynchronous (MESSAGES_PER_UPDATE).unwrap()
+    Kind::new_asynchronous (MESSAGES_PER_UPDATE, None).unwrap()
   }
 
   pub fn isochronous_default() -> Self {
-    const TICK_MS          : u32 = 1000;
+    const TICK             : time::Duration = time::Duration::from_secs (1);
     const TICKS_PER_UPDATE : u32 = 1;
-    Kind::new_isochronous (TICK_MS, TICKS_PER_UPDATE).unwrap()
+    Kind::new_isochronous (TICK, TICKS_PER_UPDATE, Sleep::Thread, CatchUp::All)
+      .unwrap()
   }
 
   pub fn mesochronous_default() -> Self {
-    const TICK_MS          : u32 = 1000;
+    const TICK             : time::Duration = time::Duration::from_secs (1);
     const TICKS_PER_UPDATE : u32 = 1;
-    Kind::new_mesochronous (TICK_MS, TICKS_PER_UPDATE).unwrap()
+    Kind::new_mesochronous (TICK, TICKS_PER_UPDATE, Sleep::Thread).unwrap()
   }
 
   pub const fn anisochronous_default() -> Self {
-    Kind::new_anisochronous()
+    Kind::Anisochronous { idle: Idle::Spin }
   }
 
-  pub fn new_asynchronous (messages_per_update : u32)
-    -> Result <Self, Vec <KindError>>
-  {
+  pub fn new_asynchronous (
+    messages_per_update : u32,
+    idle_timeout        : Option <time::Duration>
+  ) -> Result <Self, Vec <KindError>> {
     let mut errors = Vec::new();
     if messages_per_update == 0 {
       errors.push (KindError::AsynchronousZeroMessagesPerUpdate)
     }
+    if idle_timeout.is_some_and (|timeout| timeout.is_zero()) {
+      errors.push (KindError::AsynchronousZeroIdleTimeout)
+    }
     if !errors.is_empty() {
       Err (errors)
     } else {
-      Ok (Kind::Asynchronous { messages_per_update })
+      Ok (Kind::Asynchronous { messages_per_update, idle_timeout })
     }
   }
 
-  pub fn new_isochronous (tick_ms : u32, ticks_per_update : u32)
-    -> Result <Self, Vec <KindError>>
-  {
+  pub fn new_isochronous (
+    tick             : time::Duration,
+    ticks_per_update : u32,
+    sleep            : Sleep,
+    catch_up         : CatchUp
+  ) -> Result <Self, Vec <KindError>> {
     let mut errors = Vec::new();
-    if tick_ms == 0 {
-      errors.push (KindError::IsochronousZeroTickMs)
+    if tick.is_zero() {
+      errors.push (KindError::IsochronousZeroTick)
     }
     if ticks_per_update == 0 {
       errors.push (KindError::IsochronousZeroTicksPerUpdate)
@@ -934,16 +2018,18 @@ impl Kind {
     if !errors.is_empty() {
       Err (errors)
     } else {
-      Ok (Kind::Isochronous { tick_ms, ticks_per_update })
+      Ok (Kind::Isochronous { tick, ticks_per_update, sleep, catch_up })
     }
   }
 
-  pub fn new_mesochronous (tick_ms : u32, ticks_per_update : u32)
-    -> Result <Self, Vec <KindError>>
-  {
+  pub fn new_mesochronous (
+    tick             : time::Duration,
+    ticks_per_update : u32,
+    sleep            : Sleep
+  ) -> Result <Self, Vec <KindError>> {
     let mut errors = Vec::new();
-    if tick_ms == 0 {
-      errors.push (KindError::MesochronousZeroTickMs)
+    if tick.is_zero() {
+      errors.push (KindError::MesochronousZeroTick)
     }
     if ticks_per_update == 0 {
       errors.push (KindError::MesochronousZeroTicksPerUpdate)
@@ -951,13 +2037,25 @@ impl Kind {
     if !errors.is_empty() {
       Err (errors)
     } else {
-      Ok (Kind::Isochronous { tick_ms, ticks_per_update })
+      Ok (Kind::Mesochronous { tick, ticks_per_update, sleep })
     }
   }
 
-  #[inline]
-  pub const fn new_anisochronous() -> Self {
-    Kind::Anisochronous
+  pub fn new_anisochronous (idle : Idle) -> Result <Self, Vec <KindError>> {
+    let mut errors = Vec::new();
+    if let Idle::Backoff { min, max } = idle {
+      if min.is_zero() {
+        errors.push (KindError::AnisochronousZeroBackoff)
+      }
+      if max < min {
+        errors.push (KindError::AnisochronousBackoffMinExceedsMax)
+      }
+    }
+    if !errors.is_empty() {
+      Err (errors)
+    } else {
+      Ok (Kind::Anisochronous { idle })
+    }
   }
 
   fn validate_role <CTX : session::Context> (&self,
@@ -968,16 +2066,20 @@ impl Kind {
 
     match *self {
       Kind::Asynchronous {..} => {
-        // asynchronous processes must have exactly one endpoint
+        // asynchronous processes must have at least one endpoint
         if endpoints.is_empty() {
           errors.push (DefineError::AsynchronousZeroEndpoints)
-        } else if 1 < endpoints.len() {
-          errors.push (DefineError::AsynchronousMultipleEndpoints)
+        }
+      }
+      Kind::Anisochronous { idle: Idle::Park } => {
+        // parked processes must have an endpoint to wake them
+        if endpoints.is_empty() {
+          errors.push (DefineError::AnisochronousParkZeroEndpoints)
         }
       }
       Kind::Isochronous   {..} |
       Kind::Mesochronous  {..} |
-      Kind::Anisochronous      => { /* no restrictions */ }
+      Kind::Anisochronous {..} => { /* no restrictions */ }
     }
 
     if !errors.is_empty() {
@@ -998,6 +2100,59 @@ impl <M> From <Result <(), channel::SendError <M>>> for ControlFlow {
   }
 }
 
+/// A full channel is not an error for the sending process: the message is
+/// dropped and the process continues.
+impl <M> From <Result <(), channel::TrySendError <M>>> for ControlFlow {
+  fn from (send_result : Result <(), channel::TrySendError <M>>) -> Self {
+    match send_result {
+      Ok  (()) | Err (channel::TrySendError::Full (_)) => ControlFlow::Continue,
+      Err (channel::TrySendError::Disconnected (_))
+      | Err (channel::TrySendError::Protocol (_))
+      | Err (channel::TrySendError::WrongAddressing (_)) => ControlFlow::Break
+    }
+  }
+}
+
+impl CatchUp {
+  /// Number of ticks to skip when the next tick is overdue by `late`.
+  pub fn skip (self, late : time::Duration, tick : time::Duration) -> u32 {
+    // ticks that have fully elapsed after the overdue tick
+    let behind = u32::try_from (late.as_nanos() / tick.as_nanos())
+      .unwrap_or (u32::MAX);
+    match self {
+      CatchUp::All               => 0,
+      CatchUp::Limit { ticks }   => behind.saturating_sub (ticks),
+      CatchUp::Skip
+      | CatchUp::Report          => behind
+    }
+  }
+}
+
+impl Sleep {
+  /// Wait until the given deadline; returns immediately if it has passed.
+  pub fn until (self, deadline : time::Instant) {
+    match self {
+      Sleep::Thread => {
+        let now = time::Instant::now();
+        if now < deadline {
+          std::thread::sleep (deadline - now);
+        }
+      }
+      Sleep::Spin => {
+        while time::Instant::now() < deadline {
+          std::hint::spin_loop();
+        }
+      }
+      Sleep::Hybrid { spin } => {
+        if let Some (wake) = deadline.checked_sub (spin) {
+          Sleep::Thread.until (wake);
+        }
+        Sleep::Spin.until (deadline);
+      }
+    }
+  }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 //  functions                                                                 //
 ////////////////////////////////////////////////////////////////////////////////
@@ -1016,6 +2171,213 @@ pub fn report_sizes <CTX : session::Context + 'static> () {
 //  private
 //
 
+//
+//  fn shut_down
+//
+
+/// Drop the channels of a process that panicked or was aborted so that peer
+/// processes see disconnects, end the process, and report the outcome to the
+/// session. A panic also shuts down the session, since processes that do not
+/// receive from the panicked process would otherwise never end.
+fn shut_down <CTX, P, RES> (process : &mut P, outcome : Outcome <CTX::GPRES>)
+where
+  CTX : session::Context + 'static,
+  P   : Process <CTX, RES>,
+  RES : Presult <CTX, P>
+{
+  process.sourcepoints_mut().clear();
+  drop (process.endpoints_mut().take());
+  end (process);
+  if let Outcome::Panicked (_) = outcome {
+    process.supervisor().shut_down();
+  }
+  let session_handle = &process.inner_ref().as_ref().session_handle;
+  session_handle.result_tx.send (outcome).unwrap();
+}
+
+//
+//  fn panicked
+//
+
+/// Log a panic caught in a process and return the panic message.
+fn panicked <PID : std::fmt::Debug> (
+  process_id : &PID,
+  payload    : &(dyn std::any::Any + Send)
+) -> String {
+  let message = payload.downcast_ref::<&str>().map_or_else (
+    || payload.downcast_ref::<String>()
+      .map_or_else (|| "unknown panic payload".to_string(), Clone::clone),
+    |message| (*message).to_string());
+  log::error!(process:?=process_id, message=message.as_str();
+    "process panicked");
+  message
+}
+
+//
+//  fn end
+//
+
+/// Move a process that has not ended to the `Ended` state.
+fn end <CTX, P, RES> (process : &mut P) where
+  CTX : session::Context + 'static,
+  P   : Process <CTX, RES>,
+  RES : Presult <CTX, P>
+{
+  if process.state_id() == inner::StateId::Ready {
+    process.inner_mut().handle_event (inner::EventParams::Run{}.into())
+      .unwrap();
+  }
+  if process.state_id() == inner::StateId::Running {
+    process.inner_mut().handle_event (inner::EventParams::End{}.into())
+      .unwrap();
+  }
+}
+
+//
+//  fn restart_requested
+//
+
+/// Decide whether an ended process is restarted by its supervisor. No process
+/// is restarted once the session is shutting down. An interrupted process is
+/// always restarted; otherwise a restart counts against the restart intensity
+/// of the process, and a one-for-all restart interrupts every other
+/// supervised process.
+fn restart_requested <CTX, P, RES> (
+  process     : &P,
+  interrupted : bool,
+  panicked    : bool
+) -> bool where
+  CTX : session::Context + 'static,
+  P   : Process <CTX, RES>,
+  RES : Presult <CTX, P>
+{
+  if process.supervisor().is_shut_down() {
+    return false
+  }
+  if interrupted {
+    log::debug!(process:?=process.id(); "process interrupted");
+    return true
+  }
+  let supervision = *process.def().supervision();
+  let requested = match supervision.restart {
+    Restart::Never   => false,
+    Restart::OnPanic => panicked,
+    Restart::Always  => true
+  };
+  if !requested {
+    return false
+  }
+  let pid = process.id().clone().into();
+  let now = process.clock().now();
+  if !process.supervisor().try_restart (pid, now, &supervision) {
+    log::error!(process:?=process.id(), max_restarts=supervision.max_restarts,
+      period:?=supervision.period; "process restart intensity exceeded");
+    return false
+  }
+  log::warn!(process:?=process.id(), panicked, strategy:?=supervision.strategy;
+    "process restarting");
+  if supervision.strategy == Strategy::OneForAll {
+    process.supervisor().restart_all();
+  }
+  true
+}
+
+//
+//  fn restart
+//
+
+/// Reset an ended process and replace it with a new process created by
+/// `Process::new` on the same inner state, keeping its channels, clock, and
+/// supervisor.
+fn restart <CTX, P, RES> (process : &mut P) where
+  CTX : session::Context + 'static,
+  P   : Process <CTX, RES>,
+  RES : Presult <CTX, P>
+{
+  debug_assert_eq!(process.state_id(), inner::StateId::Ended);
+  // the inner state is moved out of the old process, which is left with an
+  // ended placeholder so that it can be dropped
+  let placeholder = {
+    let (result_tx, _) = mpsc::channel();
+    let (_, continuation_rx) = mpsc::channel();
+    let mut placeholder = Inner::new (inner::ExtendedState::new (
+      Some (process.def().clone()),
+      Some (session::Handle { result_tx, continuation_rx }),
+      Some (VecMap::new()),
+      Some (std::cell::RefCell::new (None)),
+      None,
+      None,
+      None,
+      None,
+      None
+    ).unwrap());
+    placeholder.handle_event (inner::EventParams::Abort{}.into()).unwrap();
+    placeholder
+  };
+  let mut inner = std::mem::replace (process.inner_mut(), placeholder);
+  inner.handle_event (inner::EventParams::Reset{}.into()).unwrap();
+  drop (std::mem::replace (process, P::new (inner)));
+}
+
+//
+//  fn select_message
+//
+
+/// Wait until a message is received on any open endpoint, receiving from each
+/// endpoint in turn starting after the last endpoint that received a message.
+/// Returns the index of the endpoint and the message, `Timeout` if the
+/// deadline is reached or the process is interrupted first, or `Disconnected`
+/// when every channel is closed.
+fn select_message <CTX : session::Context> (
+  process_id    : &CTX::PID,
+  endpoints     : &[(CTX::CID, &dyn channel::Endpoint <CTX>)],
+  open_channels : &mut [bool],
+  next_channel  : &mut usize,
+  notifier      : &channel::select::Notifier,
+  deadline      : Option <time::Instant>,
+  interrupted   : &dyn Fn() -> bool
+) -> Result <(usize, CTX::GMSG), channel::RecvTimeoutError> {
+  loop {
+    // record the generation before polling so that a message sent during the
+    // poll, or an interrupt, is not missed
+    let generation = notifier.generation();
+    if interrupted() {
+      return Err (channel::RecvTimeoutError::Timeout)
+    }
+    for offset in 0..endpoints.len() {
+      let index = (*next_channel + offset) % endpoints.len();
+      if !open_channels[index] {
+        continue
+      }
+      let (channel_id, endpoint) = &endpoints[index];
+      match endpoint.try_recv() {
+        Ok (message) => {
+          *next_channel = index + 1;
+          return Ok ((index, message))
+        }
+        Err (channel::TryRecvError::Empty) => {}
+        Err (channel::TryRecvError::Disconnected) => {
+          log::info!(process:?=process_id, channel:?=channel_id;
+            "process receive failed: sender disconnected");
+          open_channels[index] = false;
+        }
+      }
+    }
+    if !open_channels.contains (&true) {
+      return Err (channel::RecvTimeoutError::Disconnected)
+    }
+    if let Some (deadline) = deadline {
+      let now = time::Instant::now();
+      if deadline <= now {
+        return Err (channel::RecvTimeoutError::Timeout)
+      }
+      notifier.wait_timeout (generation, deadline - now);
+    } else {
+      notifier.wait (generation);
+    }
+  }
+}
+
 //
 //  fn poll_messages
 //
@@ -1025,6 +2387,7 @@ pub fn report_sizes <CTX : session::Context + 'static> () {
 fn poll_messages <CTX, P, RES> (
   process           : &mut P,
   endpoints         : &VecMap <Box <dyn channel::Endpoint <CTX>>>,
+  poller            : &mut Poller,
   open_channels     : &mut smallvec::SmallVec <[bool; 8]>,
   num_open_channels : &mut usize,
   message_count     : &mut usize)
@@ -1042,51 +2405,92 @@ where
     *num_open -= 1;
   }
 
-  // for each open channel (outer loop), poll for messages with try_recv (inner loop)
-  // until "empty" or "disconnected" is encountered
-  'poll_outer: for (open_index, (cid, endpoint)) in endpoints.iter().enumerate() {
-    #[expect(clippy::cast_possible_truncation)]
-    // NOTE: unwrap requires that err is debug
-    let Ok (channel_id) = CTX::CID::try_from (cid as u16) else { unreachable!() };
-    let channel_open = &mut open_channels[open_index];
-    if !*channel_open {
-      continue 'poll_outer
-    }
-    'poll_inner: loop {
-      match endpoint.try_recv() {
-        Ok (message) => {
-          log::debug!(
-            process:?=process.id(),
-            channel:?=channel_id,
-            message=message.inner_name().as_str();
-            "process received message");
-          *message_count += 1;
-          match process.handle_message (message) {
-            ControlFlow::Continue => {}
-            ControlFlow::Break    => {
-              channel_close (channel_open, num_open_channels);
-              // only transition to "ended" if this is the last channel to close
-              if *num_open_channels == 0 {
-                process.inner_mut().handle_event (
-                  inner::EventParams::End{}.into()
-                ).unwrap();
+  let start      = poller.next_channel;
+  let mut budget = poller.budget;
+  // in each round, for each open channel starting from the next channel (outer
+  // loop), poll for messages with try_recv (inner loop) until the channel quota
+  // is received or "empty" or "disconnected" is encountered; rounds repeat
+  // until no message is received or the budget is spent
+  'poll_rounds: loop {
+    let mut received = false;
+    'poll_outer: for (open_index, (cid, endpoint)) in endpoints.iter()
+      .enumerate().cycle().skip (start).take (endpoints.len())
+    {
+      #[expect(clippy::cast_possible_truncation)]
+      // NOTE: unwrap requires that err is debug
+      let Ok (channel_id) = CTX::CID::try_from (cid as u16) else { unreachable!() };
+      let channel_open = &mut open_channels[open_index];
+      if !*channel_open {
+        continue 'poll_outer
+      }
+      let quota     = poller.quotas[open_index];
+      let mut taken = 0;
+      'poll_inner: while quota.is_none_or (|quota| taken < quota) {
+        if budget == Some (0) {
+          poller.next_channel = open_index;
+          break 'poll_rounds
+        }
+        match endpoint.try_recv() {
+          Ok (message) => {
+            log::debug!(
+              process:?=process.id(),
+              channel:?=channel_id,
+              message=message.inner_name().as_str();
+              "process received message");
+            *message_count += 1;
+            taken    += 1;
+            received  = true;
+            budget    = budget.map (|budget| budget - 1);
+            match process.handle_message (message) {
+              ControlFlow::Continue => {}
+              ControlFlow::Break    => {
+                channel_close (channel_open, num_open_channels);
+                // only transition to "ended" if this is the last channel to
+                // close
+                if *num_open_channels == 0 {
+                  process.inner_mut().handle_event (
+                    inner::EventParams::End{}.into()
+                  ).unwrap();
+                }
+                break 'poll_inner
               }
-              break 'poll_inner
             }
           }
-        }
-        Err (channel::TryRecvError::Empty) => { break 'poll_inner }
-        Err (channel::TryRecvError::Disconnected) => {
-          log::info!(process:?=process.id(), channel:?=channel_id;
-            "process receive failed: sender disconnected");
-          channel_close (channel_open, num_open_channels);
-          if *num_open_channels == 0 {
-            process.inner_mut().handle_event (inner::EventParams::End{}.into())
-              .unwrap();
+          Err (channel::TryRecvError::Empty) => { break 'poll_inner }
+          Err (channel::TryRecvError::Disconnected) => {
+            log::info!(process:?=process.id(), channel:?=channel_id;
+              "process receive failed: sender disconnected");
+            channel_close (channel_open, num_open_channels);
+            if *num_open_channels == 0 {
+              process.inner_mut().handle_event (
+                inner::EventParams::End{}.into()
+              ).unwrap();
+            }
+            break 'poll_inner
           }
-          break 'poll_inner
-        }
-      } // end match try_recv
-    } // end 'poll_inner
-  } // end 'poll_outer
+        } // end match try_recv
+      } // end 'poll_inner
+    } // end 'poll_outer
+    if !received || poller.drain {
+      break 'poll_rounds
+    }
+  } // end 'poll_rounds
 } // end fn poll_messages
+
+//
+//  fn next_backoff
+//
+
+/// Next sleep of an `Idle::Backoff` process after sleeping for `last`. The
+/// sleep stops growing once waking after it would be out of range of the
+/// clock, and is `None` if even `min` is out of range.
+fn next_backoff (
+  last : Option <time::Duration>,
+  min  : time::Duration,
+  max  : time::Duration,
+  now  : time::Instant
+) -> Option <time::Duration> {
+  let sleep = last.map_or (min, |last| last.saturating_mul (2).min (max));
+  std::iter::once (sleep).chain (last)
+    .find (|sleep| now.checked_add (*sleep).is_some())
+}
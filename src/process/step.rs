@@ -0,0 +1,406 @@
+//! Cooperative execution of processes by a deterministic session (see
+//! `Session::run_deterministic`).
+//!
+//! Instead of running its own loop on a thread, a process is advanced one
+//! step at a time by the session. A step never blocks: it does the work that
+//! the run loop of the process kind would do next if that work is due at the
+//! current time of the session clock, and otherwise reports what the process
+//! is waiting for. A step of an `Asynchronous` process handles a single
+//! message or idle timeout, and a step of a polling process a single tick.
+//!
+//! Waits on the clock (ticks, timers, and idle backoff) are reported as
+//! deadlines instead of being slept, so the session decides when time
+//! advances.
+//!
+//! As in `Process::run`, a panic in a process method is caught and reported
+//! to the session as `Outcome::Panicked`, and the session is shut down: every
+//! other process ends on its next step.
+
+use std::time;
+use vec_map::VecMap;
+use crate::{channel, message, session};
+use super::{
+  end, inner, next_backoff, panicked, poll_messages, shut_down, ControlFlow,
+  Idle, Kind, Outcome, Poller, Presult, Process, UpdateContext
+};
+
+///////////////////////////////////////////////////////////////////////////////
+//  structs
+///////////////////////////////////////////////////////////////////////////////
+
+/// Run loop state of a process that is advanced in steps.
+pub struct Stepper <CTX : session::Context> {
+  endpoints             : VecMap <Box <dyn channel::Endpoint <CTX>>>,
+  open_channels         : smallvec::SmallVec <[bool; 8]>,
+  num_open_channels     : usize,
+  poller                : Poller,
+  /// Next tick of a timed process
+  t_next                : time::Instant,
+  t_last_update         : time::Instant,
+  /// Idle timeout deadline of an asynchronous process
+  idle_deadline         : Option <time::Instant>,
+  /// End of the idle backoff of an anisochronous process
+  wake                  : Option <time::Instant>,
+  backoff               : Option <time::Duration>,
+  /// An anisochronous process is parked until a message arrives
+  parked                : bool,
+  message_count         : usize,
+  messages_since_update : u32,
+  tick_count            : u64,
+  ticks_since_update    : u32,
+  update_count          : u64
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  enums
+///////////////////////////////////////////////////////////////////////////////
+
+/// Outcome of a single step of a process.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Step {
+  /// A message, tick, timeout, or timer was handled.
+  Progress,
+  /// An `Anisochronous` process updated without receiving any messages.
+  Idle,
+  /// Nothing is due before the given deadline, or until a message arrives
+  /// when there is no deadline.
+  Blocked (Option <time::Instant>),
+  /// The run loop ended; the process is finished with `Stepper::finish`.
+  Ended,
+  /// The process panicked and its outcome was sent to the session.
+  Panicked
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls
+///////////////////////////////////////////////////////////////////////////////
+
+impl <CTX : session::Context + 'static> Stepper <CTX> {
+  /// Initialize the process and begin its run loop. Returns `None` if
+  /// `initialize` panicked.
+  pub fn start <P, RES> (process : &mut P) -> Option <Self> where
+    P   : Process <CTX, RES>,
+    RES : Presult <CTX, P>
+  {
+    debug_assert_eq!(process.state_id(), inner::StateId::Ready);
+    if let Err (payload) = std::panic::catch_unwind (
+      std::panic::AssertUnwindSafe (|| process.initialize()))
+    {
+      let message = panicked (process.id(), payload.as_ref());
+      shut_down (process, Outcome::Panicked (message));
+      return None
+    }
+    process.inner_mut().handle_event (inner::EventParams::Run{}.into())
+      .unwrap();
+    log::debug!(process:?=process.id(), kind:?=process.kind();
+      "process start stepping");
+    let t_start           = process.clock().now();
+    let endpoints         = process.take_endpoints();
+    let poller            = Poller::new (process.def(), &endpoints);
+    let num_open_channels = endpoints.len();
+    let idle_deadline     = match *process.kind() {
+      Kind::Asynchronous { idle_timeout, .. } =>
+        idle_timeout.and_then (|timeout| t_start.checked_add (timeout)),
+      _ => None
+    };
+    Some (Stepper {
+      endpoints,
+      open_channels:         smallvec::smallvec![true; num_open_channels],
+      num_open_channels,
+      poller,
+      t_next:                t_start,
+      t_last_update:         t_start,
+      idle_deadline,
+      wake:                  None,
+      backoff:               None,
+      parked:                false,
+      message_count:         0,
+      messages_since_update: 0,
+      tick_count:            0,
+      ticks_since_update:    0,
+      update_count:          0
+    })
+  }
+
+  /// Take the next step of the process run loop, followed by any timers that
+  /// have expired.
+  pub fn step <P, RES> (&mut self, process : &mut P) -> Step where
+    P   : Process <CTX, RES>,
+    RES : Presult <CTX, P>
+  {
+    std::panic::catch_unwind (std::panic::AssertUnwindSafe (||
+      self.try_step (process)
+    )).unwrap_or_else (|payload| {
+      // endpoints held by the stepper are dropped with it
+      let message = panicked (process.id(), payload.as_ref());
+      shut_down (process, Outcome::Panicked (message));
+      Step::Panicked
+    })
+  }
+
+  /// Return the endpoints to a process whose run loop has ended, then
+  /// terminate it and send the result to the session (see
+  /// `Process::finish`).
+  pub fn finish <P, RES> (self, process : &mut P) where
+    P   : Process <CTX, RES> + 'static,
+    RES : Presult <CTX, P>
+  {
+    debug_assert_eq!(process.state_id(), inner::StateId::Ended);
+    process.put_endpoints (self.endpoints);
+    if let Err (payload) = std::panic::catch_unwind (
+      std::panic::AssertUnwindSafe (|| process.finish()))
+    {
+      let message = panicked (process.id(), payload.as_ref());
+      shut_down (process, Outcome::Panicked (message));
+    }
+  }
+
+  fn try_step <P, RES> (&mut self, process : &mut P) -> Step where
+    P   : Process <CTX, RES>,
+    RES : Presult <CTX, P>
+  {
+    debug_assert_eq!(process.state_id(), inner::StateId::Running);
+    if process.supervisor().is_shut_down() {
+      end (process);
+      return Step::Ended
+    }
+    let now       = process.clock().now();
+    let timer_due = process.timers().next_deadline()
+      .is_some_and (|deadline| deadline <= now);
+    let step = match *process.kind() {
+      Kind::Asynchronous { messages_per_update, idle_timeout } =>
+        self.step_asynchronous (process, now, messages_per_update,
+          idle_timeout),
+      Kind::Isochronous { tick, ticks_per_update, .. } =>
+        self.step_timed (process, now, ticks_per_update, self.t_next + tick),
+      Kind::Mesochronous { tick, ticks_per_update, .. } =>
+        self.step_timed (process, now, ticks_per_update, now + tick),
+      Kind::Anisochronous { idle } =>
+        self.step_anisochronous (process, now, idle, timer_due)
+    };
+    if timer_due {
+      process.handle_timers();
+    }
+    if process.state_id() != inner::StateId::Running {
+      return Step::Ended
+    }
+    match step {
+      Step::Blocked (_) | Step::Idle if timer_due => Step::Progress,
+      Step::Blocked (deadline) => Step::Blocked (deadline.into_iter()
+        .chain (process.timers().next_deadline()).min()),
+      step => step
+    }
+  }
+
+  /// Handle one message or idle timeout.
+  fn step_asynchronous <P, RES> (&mut self,
+    process             : &mut P,
+    now                 : time::Instant,
+    messages_per_update : u32,
+    idle_timeout        : Option <time::Duration>
+  ) -> Step where
+    P   : Process <CTX, RES>,
+    RES : Presult <CTX, P>
+  {
+    use message::Global;
+    if self.idle_deadline.is_some_and (|deadline| deadline <= now) {
+      log::trace!(process:?=process.id(); "process idle timeout");
+      // the timeout counts as an update of the process
+      let messages = self.messages_since_update.into();
+      self.set_update_context (process, now, self.update_count,
+        time::Duration::ZERO, messages);
+      match process.handle_timeout() {
+        ControlFlow::Continue => {}
+        ControlFlow::Break    => end (process)
+      }
+      self.update_count += 1;
+      self.messages_since_update = 0;
+      self.idle_deadline =
+        idle_timeout.and_then (|timeout| now.checked_add (timeout));
+      return Step::Progress
+    }
+    // receive from each open endpoint in turn starting after the last
+    // endpoint that received a message
+    let start = self.poller.next_channel;
+    for (index, (cid, endpoint)) in self.endpoints.iter().enumerate().cycle()
+      .skip (start).take (self.endpoints.len())
+    {
+      if !self.open_channels[index] {
+        continue
+      }
+      #[expect(clippy::cast_possible_truncation)]
+      let Ok (channel_id) = CTX::CID::try_from (cid as channel::IdReprType)
+        else { unreachable!() };
+      match endpoint.try_recv() {
+        Ok (message) => {
+          self.poller.next_channel = index + 1;
+          self.idle_deadline =
+        idle_timeout.and_then (|timeout| now.checked_add (timeout));
+          log::debug!(
+            process:?=process.id(),
+            channel:?=channel_id,
+            message=message.inner_name().as_str();
+            "process received message");
+          match process.handle_message (message) {
+            ControlFlow::Continue => {}
+            ControlFlow::Break    => self.close (process, index)
+          }
+          self.message_count         += 1;
+          self.messages_since_update += 1;
+          if messages_per_update <= self.messages_since_update {
+            let messages = self.messages_since_update.into();
+            self.update (process, now, self.update_count,
+              time::Duration::ZERO, messages);
+            self.messages_since_update = 0;
+          }
+          return Step::Progress
+        }
+        Err (channel::TryRecvError::Empty) => {}
+        Err (channel::TryRecvError::Disconnected) => {
+          log::info!(process:?=process.id(), channel:?=channel_id;
+            "process receive failed: sender disconnected");
+          self.close (process, index);
+          return Step::Progress
+        }
+      }
+    }
+    Step::Blocked (self.idle_deadline)
+  }
+
+  /// Poll and update on the next tick of an `Isochronous` or `Mesochronous`
+  /// process if it is due. Ticks are never late since the session clock does
+  /// not advance past the next tick.
+  fn step_timed <P, RES> (&mut self,
+    process          : &mut P,
+    now              : time::Instant,
+    ticks_per_update : u32,
+    t_following      : time::Instant
+  ) -> Step where
+    P   : Process <CTX, RES>,
+    RES : Presult <CTX, P>
+  {
+    if now < self.t_next {
+      return Step::Blocked (Some (self.t_next))
+    }
+    log::trace!(process:?=process.id(), tick=self.tick_count; "process tick");
+    let lateness = now.duration_since (self.t_next);
+    self.t_next  = t_following;
+    let tick_message_count = self.message_count;
+    poll_messages (process, &self.endpoints, &mut self.poller,
+      &mut self.open_channels, &mut self.num_open_channels,
+      &mut self.message_count);
+    self.tick_count         += 1;
+    self.ticks_since_update += 1;
+    if self.ticks_since_update == ticks_per_update {
+      self.update (process, now, self.tick_count - 1, lateness,
+        (self.message_count - tick_message_count) as u64);
+      self.ticks_since_update = 0;
+    }
+    Step::Progress
+  }
+
+  /// Poll and update unless the process is idle: backing off until a
+  /// deadline, or parked until a message arrives or a channel closes.
+  fn step_anisochronous <P, RES> (&mut self,
+    process   : &mut P,
+    now       : time::Instant,
+    idle      : Idle,
+    timer_due : bool
+  ) -> Step where
+    P   : Process <CTX, RES>,
+    RES : Presult <CTX, P>
+  {
+    if !timer_due && self.wake.is_some_and (|wake| now < wake) {
+      return Step::Blocked (self.wake)
+    }
+    self.wake = None;
+    let tick_message_count = self.message_count;
+    let num_open_channels  = self.num_open_channels;
+    poll_messages (process, &self.endpoints, &mut self.poller,
+      &mut self.open_channels, &mut self.num_open_channels,
+      &mut self.message_count);
+    let received = self.message_count != tick_message_count;
+    if self.parked && !received && !timer_due
+      && num_open_channels == self.num_open_channels
+    {
+      return Step::Blocked (None)
+    }
+    self.parked = false;
+    self.update (process, now, self.update_count, time::Duration::ZERO,
+      (self.message_count - tick_message_count) as u64);
+    if received {
+      self.backoff = None;
+      return Step::Progress
+    }
+    match idle {
+      Idle::Spin | Idle::Yield => Step::Idle,
+      Idle::Backoff { min, max } => {
+        self.backoff = next_backoff (self.backoff, min, max, now);
+        self.wake    = self.backoff.map (|sleep| now + sleep);
+        Step::Idle
+      }
+      Idle::Park => {
+        // the update may have sent messages, so parking takes effect on the
+        // next step
+        self.parked = true;
+        Step::Progress
+      }
+    }
+  }
+
+  fn update <P, RES> (&mut self,
+    process  : &mut P,
+    now      : time::Instant,
+    tick     : u64,
+    lateness : time::Duration,
+    messages : u64
+  ) where
+    P   : Process <CTX, RES>,
+    RES : Presult <CTX, P>
+  {
+    log::trace!(process:?=process.id(), update=self.update_count;
+      "process update");
+    self.set_update_context (process, now, tick, lateness, messages);
+    match process.update() {
+      ControlFlow::Continue => {}
+      ControlFlow::Break    => end (process)
+    }
+    self.update_count += 1;
+  }
+
+  /// Set the update context of the process for the next update.
+  fn set_update_context <P, RES> (&mut self,
+    process  : &mut P,
+    now      : time::Instant,
+    tick     : u64,
+    lateness : time::Duration,
+    messages : u64
+  ) where
+    P   : Process <CTX, RES>,
+    RES : Presult <CTX, P>
+  {
+    process.inner_mut().extended_state_mut().update_context = UpdateContext {
+      update:       self.update_count,
+      tick,
+      dt:           now.duration_since (self.t_last_update),
+      lateness,
+      messages,
+      missed_ticks: 0
+    };
+    self.t_last_update = now;
+  }
+
+  /// Close the channel at the given endpoint index, ending the process when it
+  /// was the last open channel.
+  fn close <P, RES> (&mut self, process : &mut P, index : usize) where
+    P   : Process <CTX, RES>,
+    RES : Presult <CTX, P>
+  {
+    debug_assert!(self.open_channels[index]);
+    self.open_channels[index] = false;
+    self.num_open_channels   -= 1;
+    if self.num_open_channels == 0 {
+      end (process)
+    }
+  }
+}
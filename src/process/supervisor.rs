@@ -0,0 +1,274 @@
+//! Restart and control of the processes of a session.
+//!
+//! Each process of a running session holds the `Supervisor` of the session.
+//! A process that panics or ends is restarted in its own thread according to
+//! the `Supervision` of its definition: it is rebuilt with `Process::new` on
+//! the same channels and runs again from `initialize`, as long as the number
+//! of recent restarts stays within the restart intensity.
+//!
+//! With `Strategy::OneForAll`, the restarting process also starts a new
+//! generation of the supervisor. Every other supervised process sees that its
+//! generation is out of date at the start of its next loop iteration, is
+//! woken first if it is blocked on its endpoints, and restarts after calling
+//! `terminate`.
+//!
+//! The supervisor also holds control requests made with `Session::pause`,
+//! `Session::resume`, and `Session::abort`. A paused process blocks at the
+//! start of its next loop iteration until it is resumed or aborted; an
+//! aborted process ends without calling `terminate` and is reported to the
+//! session as `Outcome::Aborted`.
+//!
+//! A shutdown requested with a `session::ShutdownHandle` ends every process at
+//! the start of its next loop iteration as if it had ended normally:
+//! `terminate` is called and the result is sent to the session. Paused and
+//! blocked processes are woken, and no process is restarted once a shutdown
+//! has been requested.
+//!
+//! A worker that panics on a job is restarted with a fresh state and handles
+//! the remaining jobs; the job it panicked on is lost:
+//!
+//! ```
+//! extern crate apis;
+//! use std::sync::atomic::{AtomicBool, Ordering};
+//!
+//! static FAILED : AtomicBool = AtomicBool::new (false);
+//!
+//! apis::def_session! {
+//!   context Jobs {
+//!     PROCESSES where
+//!       let process    = self,
+//!       let message_in = message_in
+//!     [
+//!       process Producer (sent : u64) {
+//!         kind { apis::process::Kind::anisochronous_default() }
+//!         sourcepoints [Work]
+//!         endpoints    []
+//!         handle_message { unreachable!() }
+//!         update {
+//!           process.sent += 1;
+//!           process.send (ChannelId::Work, Job::Run (process.sent)).unwrap();
+//!           if process.sent < 3 {
+//!             apis::process::ControlFlow::Continue
+//!           } else {
+//!             apis::process::ControlFlow::Break
+//!           }
+//!         }
+//!       }
+//!       process Worker (total : u64) -> (u64) {
+//!         kind { apis::process::Kind::asynchronous_default() }
+//!         sourcepoints []
+//!         endpoints    [Work]
+//!         supervise {
+//!           apis::process::Supervision {
+//!             restart: apis::process::Restart::OnPanic,
+//!             .. Default::default()
+//!           }
+//!         }
+//!         terminate { process.result = process.total }
+//!         handle_message {
+//!           let GlobalMessage::Job (Job::Run (job)) = message_in;
+//!           if job == 2 && !FAILED.swap (true, Ordering::SeqCst) {
+//!             panic!("job failed")
+//!           }
+//!           process.total += job;
+//!           apis::process::ControlFlow::Continue
+//!         }
+//!         update { apis::process::ControlFlow::Continue }
+//!       }
+//!     ]
+//!     CHANNELS [
+//!       channel Work <Job> (Simplex) {
+//!         producers [Producer]
+//!         consumers [Worker]
+//!       }
+//!     ]
+//!     MESSAGES [
+//!       message Job { Run (u64) }
+//!     ]
+//!   }
+//! }
+//!
+//! fn main() {
+//!   use apis::session::Context;
+//!   let mut session : apis::Session <Jobs> = Jobs::def().unwrap().into();
+//!   let mut outcomes = session.run_outcomes();
+//!   let Some (apis::process::Outcome::Ended (GlobalPresult::Worker (total))) =
+//!     outcomes.remove (ProcessId::Worker as usize) else { unreachable!() };
+//!   // only the job sent after the panic is counted by the restarted worker
+//!   assert_eq!(total, 3);
+//! }
+//! ```
+
+use std::collections::VecDeque;
+use std::sync::{Condvar, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::time;
+use vec_map::VecMap;
+use crate::channel;
+use super::Supervision;
+
+///////////////////////////////////////////////////////////////////////////////
+//  structs
+///////////////////////////////////////////////////////////////////////////////
+
+/// Restart and control state shared by the processes of a session.
+#[derive(Debug, Default)]
+pub struct Supervisor {
+  /// Incremented by each one-for-all restart
+  generation : AtomicU64,
+  /// Notifiers of processes that block on their endpoints
+  notifiers  : Mutex <VecMap <channel::select::Notifier>>,
+  /// Recent restart times of each process
+  restarts   : Mutex <VecMap <VecDeque <time::Instant>>>,
+  /// Pending control request of each process
+  controls   : Mutex <VecMap <Control>>,
+  /// Number of pending control requests, read without locking
+  pending    : AtomicUsize,
+  /// Signalled when a paused process is resumed or aborted
+  resumed    : Condvar,
+  /// Set once the session is asked to shut down
+  shutdown   : AtomicBool
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  enums
+///////////////////////////////////////////////////////////////////////////////
+
+/// A control request made by the session for one of its processes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Control {
+  /// Block at the start of the next loop iteration until resumed.
+  Pause,
+  /// End at the start of the next loop iteration without calling
+  /// `terminate`.
+  Abort
+}
+
+///////////////////////////////////////////////////////////////////////////////
+//  impls
+///////////////////////////////////////////////////////////////////////////////
+
+impl Supervisor {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Current generation; a process started in an older generation is
+  /// restarted.
+  pub fn generation (&self) -> u64 {
+    self.generation.load (Ordering::SeqCst)
+  }
+
+  /// Register the notifier of a process so that it is woken by a one-for-all
+  /// restart or a control request while blocked on its endpoints.
+  pub fn add_notifier (&self,
+    process : usize, notifier : channel::select::Notifier
+  ) {
+    self.notifiers.lock().unwrap().insert (process, notifier);
+  }
+
+  /// Record a restart of the given process at the given time, unless the
+  /// process has already been restarted `max_restarts` times within the
+  /// last `period`. Returns false if the restart intensity is exceeded.
+  pub fn try_restart (&self,
+    process     : usize,
+    now         : time::Instant,
+    supervision : &Supervision
+  ) -> bool {
+    let mut restarts = self.restarts.lock().unwrap();
+    let times = restarts.entry (process).or_insert_with (VecDeque::new);
+    while times.front()
+      .is_some_and (|time| supervision.period <= now.duration_since (*time))
+    {
+      times.pop_front();
+    }
+    let allowed = times.len() < supervision.max_restarts as usize;
+    if allowed {
+      times.push_back (now);
+    }
+    drop (restarts);
+    allowed
+  }
+
+  /// Start a new generation, restarting every running supervised process.
+  pub fn restart_all (&self) {
+    self.generation.fetch_add (1, Ordering::SeqCst);
+    let notifiers = self.notifiers.lock().unwrap();
+    notifiers.values().for_each (channel::select::Notifier::notify);
+  }
+
+  /// Request that the given process pause. Has no effect on an aborted
+  /// process.
+  pub fn pause (&self, process : usize) {
+    let mut controls = self.controls.lock().unwrap();
+    if !controls.contains_key (process) {
+      controls.insert (process, Control::Pause);
+      self.pending.fetch_add (1, Ordering::SeqCst);
+    }
+    drop (controls);
+    self.notify (process);
+  }
+
+  /// Resume the given process if it is paused or has a pending pause
+  /// request.
+  pub fn resume (&self, process : usize) {
+    let mut controls = self.controls.lock().unwrap();
+    if controls.get (process) == Some (&Control::Pause) {
+      controls.remove (process);
+      self.pending.fetch_sub (1, Ordering::SeqCst);
+    }
+    drop (controls);
+    self.resumed.notify_all();
+  }
+
+  /// Request that the given process abort.
+  pub fn abort (&self, process : usize) {
+    let mut controls = self.controls.lock().unwrap();
+    if controls.insert (process, Control::Abort).is_none() {
+      self.pending.fetch_add (1, Ordering::SeqCst);
+    }
+    drop (controls);
+    self.resumed.notify_all();
+    self.notify (process);
+  }
+
+  /// Pending control request of the given process.
+  pub fn control (&self, process : usize) -> Option <Control> {
+    if self.pending.load (Ordering::SeqCst) == 0 {
+      return None
+    }
+    self.controls.lock().unwrap().get (process).copied()
+  }
+
+  /// Block a paused process until it is resumed, aborted, or the session is
+  /// shut down. Returns `Some (Control::Abort)` if the process was aborted.
+  pub fn wait_resumed (&self, process : usize) -> Option <Control> {
+    let controls = self.resumed.wait_while (self.controls.lock().unwrap(),
+      |controls| controls.get (process) == Some (&Control::Pause)
+        && !self.is_shut_down()
+    ).unwrap();
+    controls.get (process).copied()
+  }
+
+  /// Ask every process to end, waking processes that are paused or blocked
+  /// on their endpoints.
+  pub fn shut_down (&self) {
+    self.shutdown.store (true, Ordering::SeqCst);
+    // lock so that a process about to wait while paused sees the flag
+    drop (self.controls.lock().unwrap());
+    self.resumed.notify_all();
+    let notifiers = self.notifiers.lock().unwrap();
+    notifiers.values().for_each (channel::select::Notifier::notify);
+  }
+
+  /// True once a shutdown has been requested.
+  pub fn is_shut_down (&self) -> bool {
+    self.shutdown.load (Ordering::SeqCst)
+  }
+
+  fn notify (&self, process : usize) {
+    if let Some (notifier) = self.notifiers.lock().unwrap().get (process) {
+      notifier.notify();
+    }
+  }
+}
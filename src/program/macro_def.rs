@@ -74,12 +74,18 @@ macro_rules! def_program {
           pub main_process :
             Option <Box <
               <$mode_mod::$mode_context as $crate::session::Context>::GPROC
-            >>
+            >>,
+          pub clock :
+            Option <std::sync::Arc <dyn $crate::process::Clock>>,
+          pub supervisor :
+            Option <std::sync::Arc <$crate::process::Supervisor>>
         }
         let mut $mode_mod : $mode_context = $mode_context {
           channels:        None,
           process_handles: None,
-          main_process:    None
+          main_process:    None,
+          clock:           None,
+          supervisor:      None
         };
         )+
 
@@ -94,6 +100,14 @@ macro_rules! def_program {
               if $mode_mod.channels.is_none() {
                 $mode_mod.channels = Some (session.as_ref().def.create_channels());
               }
+              // continued processes run on the clock of the previous session
+              // and are supervised with the other processes of this session
+              if let Some (clock) = $mode_mod.clock.take() {
+                session.as_mut().clock = clock;
+              }
+              if let Some (supervisor) = $mode_mod.supervisor.take() {
+                session.as_mut().supervisor = supervisor;
+              }
               #[allow(unused_variables)]
               #[allow(unused_mut)]
               let mut $result = session.run_with (
@@ -133,6 +147,9 @@ macro_rules! def_program {
                       = $target_mod::$target_context::def().unwrap();
                     let mut channels = target_session_def.create_channels();
                     let mut process_handles = $crate::vec_map::VecMap::new();
+                    let clock = _session.clock().clone();
+                    let supervisor = std::sync::Arc::new (
+                      $crate::process::Supervisor::new());
 
                     // handle continuations
                     $($({
@@ -195,12 +212,19 @@ macro_rules! def_program {
                           assert!(endpoints.insert (cid, endpoint).is_none());
                         }
                       }
+                      // a process blocked on its endpoints is woken by
+                      // control requests, restarts, and shutdown
+                      if let Some (notifier)
+                        = endpoints.values().find_map (|e| e.notifier())
+                      {
+                        supervisor.add_notifier (next_pid, notifier.clone());
+                      }
 
                       // session control channels
                       let (result_tx, result_rx) =
-                        std::sync::mpsc::channel::
-                          <<$target_mod::$target_context
-                            as $crate::session::Context>::GPRES>();
+                        std::sync::mpsc::channel::<$crate::process::Outcome <
+                          <$target_mod::$target_context
+                            as $crate::session::Context>::GPRES>>();
                       let (continuation_tx, continuation_rx) =
                         std::sync::mpsc::channel::<Box <
                           dyn FnOnce (<$target_mod::$target_context
@@ -212,6 +236,8 @@ macro_rules! def_program {
                       let session_handle =
                         $crate::session::Handle::<$target_mod::$target_context> {
                           result_tx, continuation_rx };
+                      let process_clock      = clock.clone();
+                      let process_supervisor = supervisor.clone();
 
                       // closure that constructs the new process from the old,
                       // calling any custom closure code
@@ -228,7 +254,12 @@ macro_rules! def_program {
                             Some (next_process_id.def()),
                             Some (session_handle),
                             Some (sourcepoints),
-                            Some (::std::cell::RefCell::new (Some (endpoints)))
+                            Some (::std::cell::RefCell::new (Some (endpoints))),
+                            None,
+                            None,
+                            Some (process_clock),
+                            Some (process_supervisor),
+                            None
                           ).unwrap()
                         );
                         #[allow(unused_variables)]
@@ -330,6 +361,8 @@ macro_rules! def_program {
                     $target_mod.channels = Some (channels);
                     debug_assert!($target_mod.process_handles.is_none());
                     $target_mod.process_handles = Some (process_handles);
+                    $target_mod.clock = Some (clock);
+                    $target_mod.supervisor = Some (supervisor);
                   } // end branch source context
                   _ => unreachable!(
                     "transition source session should match current session")
@@ -40,8 +40,10 @@
 ///     [
 ///       process Chargen (update_count : u64) {
 ///         kind { process::Kind::Isochronous {
-///           tick_ms: 20,
-///           ticks_per_update: 1 } }
+///           tick:             std::time::Duration::from_millis (20),
+///           ticks_per_update: 1,
+///           sleep:            process::Sleep::Thread,
+///           catch_up:         process::CatchUp::All } }
 ///         sourcepoints [Charstream]
 ///         endpoints    []
 ///         handle_message { apis::process::ControlFlow::Break }
@@ -75,7 +77,154 @@
 ///
 /// The `handle_message` and `update` definitions have been ommitted for
 /// brevity, but in general any block of code can be substituted that
-/// references the `self` and `message_in` bindings.
+/// references the `self` and `message_in` bindings. Within `update`, the tick
+/// number, elapsed time, and lateness of the update are available from
+/// `process.update_context()`.
+///
+/// Polling processes receive from each endpoint until it is empty on every
+/// tick. An optional `poll` policy given after `endpoints` receives in rounds
+/// instead, so that a flooded channel does not delay messages on the others:
+///
+/// ```text
+/// process Controller () {
+///   kind           { process::Kind::isochronous_default() }
+///   sourcepoints   []
+///   endpoints      [Control, Samples]
+///   poll           { process::Poll::Weighted { weights: vec![1, 8] } }
+///   handle_message { process::ControlFlow::Continue }
+///   update         { process::ControlFlow::Continue }
+/// }
+/// ```
+///
+/// An optional `supervise` block after `poll` restarts the process with
+/// `Process::new` on the same channels when it panics or ends (see
+/// `process::supervisor`):
+///
+/// ```text
+/// process Worker (jobs : u64) {
+///   kind           { process::Kind::asynchronous_default() }
+///   sourcepoints   []
+///   endpoints      [Jobs]
+///   supervise      {
+///     process::Supervision {
+///       restart: process::Restart::OnPanic,
+///       .. Default::default()
+///     }
+///   }
+///   handle_message { process::ControlFlow::Continue }
+///   update         { process::ControlFlow::Continue }
+/// }
+/// ```
+///
+/// Optional `initialize`, `terminate`, `handle_timeout`, and `handle_timer`
+/// blocks may be given after `endpoints`, `poll`, and `supervise`, in that
+/// order.
+/// `handle_timeout` is called when an asynchronous process with an idle
+/// timeout receives no message in time:
+///
+/// ```text
+/// process Watchdog () {
+///   kind {
+///     process::Kind::Asynchronous {
+///       messages_per_update: 1,
+///       idle_timeout:        Some (std::time::Duration::from_millis (500))
+///     }
+///   }
+///   sourcepoints   []
+///   endpoints      [Heartbeat]
+///   handle_timeout { process::ControlFlow::Break }
+///   handle_message { process::ControlFlow::Continue }
+///   update         { process::ControlFlow::Continue }
+/// }
+/// ```
+///
+/// `handle_timer` is called with the identifier bound to the given name when a
+/// timer set with `process.set_timer (delay)` or `process.set_interval
+/// (period)` expires:
+///
+/// ```text
+/// process Reporter (report : Option <process::TimerId>) {
+///   kind           { process::Kind::asynchronous_default() }
+///   sourcepoints   []
+///   endpoints      [Samples]
+///   initialize     {
+///     process.report =
+///       Some (process.set_interval (Duration::from_secs (5)).unwrap());
+///   }
+///   handle_timer (timer) {
+///     debug_assert_eq!(Some (timer), process.report);
+///     process::ControlFlow::Continue
+///   }
+///   handle_message { process::ControlFlow::Continue }
+///   update         { process::ControlFlow::Continue }
+/// }
+/// ```
+///
+/// Channels are unbounded by default. An optional `capacity` makes the
+/// channel bounded so that `send` blocks and `try_send` returns
+/// `TrySendError::Full` when a consumer queue is at capacity:
+///
+/// ```text
+/// channel Charstream <Charstreammessage> (Simplex) {
+///   producers [Chargen]
+///   consumers [Upcase]
+///   capacity  { 16 }
+/// }
+/// ```
+///
+/// A `Session` channel is a bidirectional channel between one producer
+/// (client) and one consumer (server) that both list the channel as a
+/// sourcepoint and as an endpoint. Its `protocol` is a sequence of steps
+/// separated by `;`, where each step is a choice of message variants
+/// separated by `|`. Steps alternate direction starting from the producer,
+/// and the protocol finishes with `End` or `Repeat`. Naming a variant that
+/// does not exist is a compile error; sending a message that is out of order
+/// returns `SendError::Protocol`:
+///
+/// ```text
+/// channel Rpc <Rpcmessage> (Session) {
+///   producers [Client]
+///   consumers [Server]
+///   protocol  { Request ; Reply ; Repeat }
+/// }
+/// ```
+///
+/// A `Sink` channel may list `external` among its producers, alone or with
+/// producer processes, so that threads outside of the session can send into
+/// it with the injectors returned by `Session::injector` (see
+/// `channel::external`):
+///
+/// ```text
+/// channel Events <Event> (Sink) {
+///   producers [external, Timer]
+///   consumers [Handler]
+/// }
+/// ```
+///
+/// Likewise a `Simplex` or `Sink` channel may have `external` as its only
+/// consumer, so that messages sent by its producers are received outside of
+/// the session through the observer returned by `Session::observer`:
+///
+/// ```text
+/// channel Output <Outputmessage> (Simplex) {
+///   producers [Renderer]
+///   consumers [external]
+/// }
+/// ```
+///
+/// A `Broadcast` channel delivers a clone of every message sent to each
+/// consumer, so its message type must be `Clone`. Attributes given before a
+/// message definition are applied to the generated enum:
+///
+/// ```text
+/// MESSAGES [
+///   #[derive(Clone)]
+///   message Tickmessage {
+///     Tick (u64),
+///     Quit
+///   }
+/// ]
+/// ```
 
 #[macro_export]
 macro_rules! def_session {
@@ -91,8 +240,12 @@ macro_rules! def_session {
           kind { $process_kind:expr }
           sourcepoints [ $($sourcepoint:ident),* ]
           endpoints    [ $($endpoint:ident),* ]
+          $(poll { $poll:expr })?
+          $(supervise { $supervise:expr })?
           $(initialize   $initialize:block)*
           $(terminate    $terminate:block)*
+          $(handle_timeout $handle_timeout:block)*
+          $(handle_timer ($timer:ident) $handle_timer:block)*
           handle_message $handle_message:block
           update         $update:block
         })+
@@ -101,10 +254,13 @@ macro_rules! def_session {
         $(channel $channel:ident <$local_type:ident> ($kind:ident) {
           producers [ $($producer:ident),+ ]
           consumers [ $($consumer:ident),+ ]
+          $(capacity { $capacity:expr })?
+          $(protocol { $($protocol:tt)+ })?
         })*
       ]
       MESSAGES [
-        $(message $message_type:ident $message_variants:tt)*
+        $($(#[$message_attr:meta])* message $message_type:ident
+          $message_variants:tt)*
       ]
       $(main: $main_process:ident)*
     }
@@ -138,6 +294,7 @@ macro_rules! def_session {
     //  messages
     //
     $(
+    $(#[$message_attr])*
     #[derive(Debug, $crate::strum::Display)]
     pub enum $message_type $message_variants
     )*
@@ -157,6 +314,22 @@ macro_rules! def_session {
       $($process),+
     }
     $crate::def_session!(@channel_id { $($channel),* });
+
+    /// Typed channel markers for compile-time checked sends.
+    pub mod channels {
+      $(
+      #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+      pub struct $channel;
+      impl $crate::channel::TypedId <super::$context> for $channel {
+        type Message = super::$local_type;
+        const ID : super::ChannelId = super::ChannelId::$channel;
+      }
+      $crate::def_session!(
+        @external_marker $context $channel Injectable $($producer)+);
+      $crate::def_session!(
+        @external_marker $context $channel Observable $($consumer)+);
+      )*
+    }
     $crate::def_session!(@message_id { $($message_type),* });
 
     impl TryFrom <$crate::process::IdReprType> for ProcessId {
@@ -347,6 +520,24 @@ macro_rules! def_session {
         $terminate
       }
       )*
+      $(
+      fn handle_timeout (&mut self) -> $crate::process::ControlFlow {
+        #[allow(unused_variables)]
+        let $process_self = self;
+        $handle_timeout
+      }
+      )*
+      $(
+      fn handle_timer (&mut self, timer : $crate::process::TimerId)
+        -> $crate::process::ControlFlow
+      {
+        #[allow(unused_variables)]
+        let $process_self = self;
+        #[allow(unused_variables)]
+        let $timer        = timer;
+        $handle_timer
+      }
+      )*
       fn handle_message (&mut self, message : GlobalMessage)
         -> $crate::process::ControlFlow
       {
@@ -396,6 +587,29 @@ macro_rules! def_session {
           $(GlobalProcess::$process (ref mut process) => process.run()),+
         }
       }
+      fn start_steps (&mut self)
+        -> Option <$crate::process::step::Stepper <$context>>
+      {
+        match *self {
+          $(GlobalProcess::$process (ref mut process) =>
+            $crate::process::step::Stepper::start (process)
+          ),+
+        }
+      }
+      fn step (&mut self,
+        stepper : &mut $crate::process::step::Stepper <$context>
+      ) -> $crate::process::step::Step {
+        match *self {
+          $(GlobalProcess::$process (ref mut process) => stepper.step (process)),+
+        }
+      }
+      fn finish_steps (&mut self,
+        stepper : $crate::process::step::Stepper <$context>
+      ) {
+        match *self {
+          $(GlobalProcess::$process (ref mut process) => stepper.finish (process)),+
+        }
+      }
     }
 
     //
@@ -410,12 +624,15 @@ macro_rules! def_session {
       fn def (&self) -> $crate::process::Def <$context> {
         match *self {
           $(
-          ProcessId::$process => $crate::process::Def::define (
-            self.clone(),
-            $process_kind,
-            vec![$(ChannelId::$sourcepoint),*],
-            vec![$(ChannelId::$endpoint),*]
-          ).unwrap()
+          ProcessId::$process =>
+            $crate::process::Def::define_with_supervision (
+              self.clone(),
+              $process_kind,
+              vec![$(ChannelId::$sourcepoint),*],
+              vec![$(ChannelId::$endpoint),*],
+              $crate::def_session!(@expr_default $($poll)?),
+              $crate::def_session!(@expr_default $($supervise)?)
+            ).unwrap()
           ),+
         }
       }
@@ -455,11 +672,18 @@ macro_rules! def_session {
         match *self {
           $(
           ChannelId::$channel => {
-            $crate::channel::Def::define (
+            $($crate::def_session!(
+              @protocol_variants $local_type { $($protocol)+ });)?
+            $crate::channel::Def::define_with_external (
               self.clone(),
-              $crate::channel::Kind::$kind,
-              vec![$(ProcessId::$producer),+],
-              vec![$(ProcessId::$consumer),+]
+              $crate::def_session!(@channel_kind $kind $({ $($protocol)+ })?),
+              $crate::def_session!(@process_ids [] $($producer)+),
+              $crate::def_session!(@process_ids [] $($consumer)+),
+              $crate::def_session!(@expr_option $($capacity)?),
+              $crate::channel::External {
+                producer: $crate::def_session!(@is_external $($producer)+),
+                consumer: $crate::def_session!(@is_external $($consumer)+)
+              }
             ).unwrap()
           }
           )*
@@ -480,7 +704,8 @@ macro_rules! def_session {
       {
         #[allow(unreachable_patterns)]
         match *def.id() {
-          $(ChannelId::$channel => def.to_channel::<$local_type>(),)*
+          $(ChannelId::$channel =>
+            $crate::def_session!(@to_channel $kind, def, $local_type),)*
           _ => unreachable!("can't create channel for nullary channel id")
         }
       }
@@ -597,6 +822,95 @@ macro_rules! def_session {
     }
   };
 
+  //
+  //  @channel_kind: session channels are parameterized by a protocol
+  //
+  ( @channel_kind Session { $($protocol:tt)+ } ) => {
+    $crate::channel::Kind::Session (
+      $crate::channel::Protocol::parse (stringify!($($protocol)+)))
+  };
+  ( @channel_kind Session ) => {
+    compile_error!("Session channel requires a protocol")
+  };
+  ( @channel_kind $kind:ident { $($protocol:tt)+ } ) => {
+    compile_error!("only Session channels may be given a protocol")
+  };
+
+  //
+  //  @channel_kind: other kinds
+  //
+  ( @channel_kind $kind:ident ) => { $crate::channel::Kind::$kind };
+
+  //
+  //  @protocol_variants: check that each protocol step names a variant of
+  //  the local message type
+  //
+  ( @protocol_variants $local_type:ident { End } ) => {};
+  ( @protocol_variants $local_type:ident { Repeat } ) => {};
+  ( @protocol_variants $local_type:ident { $ending:ident } ) => {
+    compile_error!("protocol must end with `End` or `Repeat`")
+  };
+  ( @protocol_variants $local_type:ident {
+      $variant:ident $separator:tt $($rest:tt)+
+    }
+  ) => {
+    let _ = |message : &$local_type|
+      matches!(*message, $local_type::$variant {..});
+    $crate::def_session!(@protocol_variants $local_type { $($rest)+ });
+  };
+
+  //
+  //  @process_ids: process ids of a role, excluding `external`
+  //
+  ( @process_ids [$($pid:ident)*] ) => { vec![$(ProcessId::$pid),*] };
+  ( @process_ids [$($pid:ident)*] external $($rest:ident)* ) => {
+    $crate::def_session!(@process_ids [$($pid)*] $($rest)*)
+  };
+  ( @process_ids [$($pid:ident)*] $next:ident $($rest:ident)* ) => {
+    $crate::def_session!(@process_ids [$($pid)* $next] $($rest)*)
+  };
+
+  //
+  //  @is_external: a role includes `external`
+  //
+  ( @is_external ) => { false };
+  ( @is_external external $($rest:ident)* ) => { true };
+  ( @is_external $next:ident $($rest:ident)* ) => {
+    $crate::def_session!(@is_external $($rest)*)
+  };
+
+  //
+  //  @external_marker: mark channels with an external producer
+  //  (`Injectable`) or consumer (`Observable`)
+  //
+  ( @external_marker $context:ident $channel:ident $marker:ident ) => {};
+  ( @external_marker $context:ident $channel:ident $marker:ident
+    external $($rest:ident)*
+  ) => {
+    impl $crate::channel::$marker <super::$context> for $channel {}
+  };
+  ( @external_marker $context:ident $channel:ident $marker:ident
+    $next:ident $($rest:ident)*
+  ) => {
+    $crate::def_session!(@external_marker $context $channel $marker $($rest)*);
+  };
+
+  //
+  //  @to_channel: broadcast channels require a Clone message type
+  //
+  ( @to_channel Broadcast, $def:ident, $local_type:ident ) => {
+    $def.to_broadcast_channel::<$local_type>()
+      .expect ("channel def should match its kind")
+  };
+
+  //
+  //  @to_channel: other kinds
+  //
+  ( @to_channel $kind:ident, $def:ident, $local_type:ident ) => {
+    $def.to_channel::<$local_type>()
+      .expect ("channel def should match its kind")
+  };
+
   //
   //  @expr_option: Some (expr)
   //
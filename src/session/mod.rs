@@ -7,6 +7,8 @@ use strum::{EnumCount, IntoEnumIterator};
 use crate::{channel, message, process};
 
 mod macro_def;
+#[cfg(all(feature = "signal", target_os = "linux"))]
+mod signal;
 
 ////////////////////////////////////////////////////////////////////////////////
 //  typedefs
@@ -26,7 +28,11 @@ def_machine_nodefault! {
   Session <CTX : { Context }> (
     def             : Def <CTX>,
     process_handles : vec_map::VecMap <process::Handle <CTX>>,
-    main_process    : Option <Box <CTX::GPROC>>
+    main_process    : Option <Box <CTX::GPROC>>,
+    clock           : std::sync::Arc <dyn process::Clock>
+      = std::sync::Arc::new (process::clock::System),
+    supervisor      : std::sync::Arc <process::Supervisor> = Default::default(),
+    channels        : Option <vec_map::VecMap <channel::Channel <CTX>>> = None
   ) @ _session {
     STATES [
       state Ready   ()
@@ -57,12 +63,42 @@ pub struct Def <CTX : Context> {
   process_def : vec_map::VecMap <process::Def <CTX>>
 }
 
+/// Sends control requests to the processes of a session from any thread
+/// (see `Session::controller`).
+#[derive(Clone, Debug)]
+pub struct Controller <CTX : Context> {
+  supervisor : std::sync::Arc <process::Supervisor>,
+  context    : std::marker::PhantomData <CTX>
+}
+
+/// Requests a graceful shutdown of a running session from any thread (see
+/// `Session::shutdown_handle`).
+#[derive(Clone, Debug)]
+pub struct ShutdownHandle {
+  supervisor : std::sync::Arc <process::Supervisor>
+}
+
+/// Handle to a session running on its own thread (see `Session::spawn`).
+// distinguished from the `Handle` held by processes
+#[expect(clippy::module_name_repetitions)]
+#[derive(Debug)]
+pub struct SessionHandle <CTX : Context> {
+  results_rx      : std::sync::mpsc::Receiver <vec_map::VecMap <CTX::GPRES>>,
+  /// Joined once the results are received
+  thread          : Option <std::thread::JoinHandle <()>>,
+  controller      : Controller <CTX>,
+  shutdown_handle : ShutdownHandle
+}
+
 /// Handle to the session held by processes.
 pub struct Handle <CTX : Context> {
-  pub result_tx       : std::sync::mpsc::Sender <CTX::GPRES>,
+  pub result_tx       : std::sync::mpsc::Sender <process::Outcome <CTX::GPRES>>,
   pub continuation_rx : std::sync::mpsc::Receiver <Continuation <CTX>>
 }
 
+/// `SplitMix64` generator ordering the steps of a deterministic session.
+struct Rng (u64);
+
 ////////////////////////////////////////////////////////////////////////////////
 //  enums
 ////////////////////////////////////////////////////////////////////////////////
@@ -252,37 +288,644 @@ impl <CTX : Context> Session <CTX> {
     self.def().name
   }
 
+  /// Run the processes of this session on the given clock instead of the
+  /// system clock (see `process::clock`). A program runs processes continued
+  /// into its next session, and that session, on the same clock.
+  pub fn with_clock (mut self, clock : std::sync::Arc <dyn process::Clock>)
+    -> Self
+  {
+    debug_assert_eq!(self.state_id(), StateId::Ready);
+    self.as_mut().clock = clock;
+    self
+  }
+
+  pub fn clock (&self) -> &std::sync::Arc <dyn process::Clock> {
+    &self.extended_state().clock
+  }
+
+  /// A handle for pausing, resuming, and aborting the processes of this
+  /// session from other threads while it runs.
+  ///
+  /// Pause a simulation before it starts, then abort it; the display process
+  /// receiving from it stays live and ends when the simulation channel
+  /// disconnects:
+  ///
+  /// ```
+  /// extern crate apis;
+  ///
+  /// apis::def_session! {
+  ///   context Sim {
+  ///     PROCESSES where
+  ///       let process    = self,
+  ///       let message_in = message_in
+  ///     [
+  ///       process Simulation (step : u64) {
+  ///         kind { apis::process::Kind::Isochronous {
+  ///           tick:             std::time::Duration::from_millis (1),
+  ///           ticks_per_update: 1,
+  ///           sleep:            apis::process::Sleep::Thread,
+  ///           catch_up:         apis::process::CatchUp::All
+  ///         } }
+  ///         sourcepoints [Frames]
+  ///         endpoints    []
+  ///         handle_message { unreachable!() }
+  ///         update {
+  ///           process.step += 1;
+  ///           let frame = Frame::Step (process.step);
+  ///           let _ = process.send (ChannelId::Frames, frame);
+  ///           apis::process::ControlFlow::Continue
+  ///         }
+  ///       }
+  ///       process Display (frames : u64) -> (u64) {
+  ///         kind { apis::process::Kind::asynchronous_default() }
+  ///         sourcepoints []
+  ///         endpoints    [Frames]
+  ///         terminate { process.result = process.frames }
+  ///         handle_message {
+  ///           process.frames += 1;
+  ///           apis::process::ControlFlow::Continue
+  ///         }
+  ///         update { apis::process::ControlFlow::Continue }
+  ///       }
+  ///     ]
+  ///     CHANNELS [
+  ///       channel Frames <Frame> (Simplex) {
+  ///         producers [Simulation]
+  ///         consumers [Display]
+  ///       }
+  ///     ]
+  ///     MESSAGES [
+  ///       message Frame { Step (u64) }
+  ///     ]
+  ///   }
+  /// }
+  ///
+  /// fn main() {
+  ///   use apis::session::Context;
+  ///   use apis::process::Outcome;
+  ///   let mut session : apis::Session <Sim> = Sim::def().unwrap().into();
+  ///   let controller = session.controller();
+  ///   controller.pause (ProcessId::Simulation);
+  ///   let control = std::thread::spawn (move || {
+  ///     std::thread::sleep (std::time::Duration::from_millis (20));
+  ///     controller.abort (ProcessId::Simulation);
+  ///   });
+  ///   let mut outcomes = session.run_outcomes();
+  ///   control.join().unwrap();
+  ///   assert!(matches!(outcomes.remove (ProcessId::Simulation as usize),
+  ///     Some (Outcome::Aborted)));
+  ///   // the simulation never ran a step
+  ///   assert!(matches!(outcomes.remove (ProcessId::Display as usize),
+  ///     Some (Outcome::Ended (GlobalPresult::Display (0)))));
+  /// }
+  /// ```
+  pub fn controller (&self) -> Controller <CTX> {
+    Controller {
+      supervisor: self.extended_state().supervisor.clone(),
+      context:    std::marker::PhantomData
+    }
+  }
+
+  /// A handle for shutting down this session from other threads while it
+  /// runs. Each process ends at the start of its next run loop iteration,
+  /// waking if it is blocked on its endpoints or paused, and `terminate` is
+  /// called so that results are collected as if every process had ended on
+  /// its own. Supervised processes are not restarted.
+  ///
+  /// A process that would otherwise run forever:
+  ///
+  /// ```
+  /// extern crate apis;
+  ///
+  /// apis::def_session! {
+  ///   context Server {
+  ///     PROCESSES where
+  ///       let process    = self,
+  ///       let message_in = message_in
+  ///     [
+  ///       process Heartbeat (beats : u64) -> (u64) {
+  ///         kind { apis::process::Kind::Isochronous {
+  ///           tick:             std::time::Duration::from_millis (1),
+  ///           ticks_per_update: 1,
+  ///           sleep:            apis::process::Sleep::Thread,
+  ///           catch_up:         apis::process::CatchUp::All
+  ///         } }
+  ///         sourcepoints []
+  ///         endpoints    []
+  ///         terminate      { process.result = process.beats }
+  ///         handle_message { unreachable!() }
+  ///         update {
+  ///           process.beats += 1;
+  ///           apis::process::ControlFlow::Continue
+  ///         }
+  ///       }
+  ///     ]
+  ///     CHANNELS  []
+  ///     MESSAGES  []
+  ///   }
+  /// }
+  ///
+  /// fn main() {
+  ///   use apis::session::Context;
+  ///   let mut session : apis::Session <Server> =
+  ///     Server::def().unwrap().into();
+  ///   let shutdown = session.shutdown_handle();
+  ///   std::thread::spawn (move || {
+  ///     std::thread::sleep (std::time::Duration::from_millis (20));
+  ///     shutdown.shut_down();
+  ///   });
+  ///   let results = session.run();
+  ///   let GlobalPresult::Heartbeat (beats) =
+  ///     results[ProcessId::Heartbeat as usize];
+  ///   assert!(0 < beats);
+  /// }
+  /// ```
+  pub fn shutdown_handle (&self) -> ShutdownHandle {
+    ShutdownHandle { supervisor: self.extended_state().supervisor.clone() }
+  }
+
+  /// Pause a process at the start of its next run loop iteration (see
+  /// `Controller::pause`).
+  pub fn pause (&self, pid : CTX::PID) {
+    self.controller().pause (pid)
+  }
+
+  /// Resume a paused process (see `Controller::resume`).
+  pub fn resume (&self, pid : CTX::PID) {
+    self.controller().resume (pid)
+  }
+
+  /// Abort a process (see `Controller::abort`).
+  pub fn abort (&self, pid : CTX::PID) {
+    self.controller().abort (pid)
+  }
+
+  /// A handle for sending messages into a channel with an external producer
+  /// from other threads (see `channel::external`). Injectors must be taken
+  /// before the session is run with `run`, `run_outcomes`,
+  /// `run_deterministic`, or `spawn`; messages sent before the session runs
+  /// are queued.
+  pub fn injector <C : channel::Injectable <CTX>> (&mut self, _channel : C)
+    -> channel::Injector <CTX, C::Message>
+  where
+    CTX : 'static
+  {
+    debug_assert_eq!(self.state_id(), StateId::Ready);
+    let extended_state = self.as_mut();
+    let channels = extended_state.channels
+      .get_or_insert_with (|| extended_state.def.create_channels());
+    let sourcepoint = channels[C::ID.into()].injector.clone()
+      .expect ("injectable channel should have an injector");
+    channel::Injector::new (C::ID, sourcepoint)
+  }
+
+  /// The receiver of a channel with an external consumer, for use from
+  /// another thread while the session runs (see `channel::external`). The
+  /// observer must be taken before the session is run with `run`,
+  /// `run_outcomes`, `run_deterministic`, or `spawn`.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the observer of the channel has already been taken.
+  pub fn observer <C : channel::Observable <CTX>> (&mut self, _channel : C)
+    -> channel::Observer <CTX, C::Message>
+  where
+    CTX : 'static
+  {
+    debug_assert_eq!(self.state_id(), StateId::Ready);
+    let extended_state = self.as_mut();
+    let channels = extended_state.channels
+      .get_or_insert_with (|| extended_state.def.create_channels());
+    let endpoint = channels[C::ID.into()].observer.take()
+      .expect ("observer of channel should not already be taken");
+    channel::Observer::new (C::ID, endpoint)
+  }
+
   /// Creates a new session and runs to completion.
   ///
   /// Transitions from `Ready` to `Running`, starts processes not already
   /// running (those present in the `process_handles` argument), waits for
   /// results and finally transitions to `Ended`.
-  pub fn run (&mut self) -> vec_map::VecMap <CTX::GPRES> {
-    let channels = self.as_ref().def.create_channels();
+  ///
+  /// Processes that panicked have no result; use `run_outcomes` to tell them
+  /// apart.
+  pub fn run (&mut self) -> vec_map::VecMap <CTX::GPRES> where
+    CTX : 'static
+  {
+    let channels = self.take_channels();
     self.run_with (channels, vec_map::VecMap::new(), None)
   }
 
+  /// Run the session to completion on a new thread without blocking the
+  /// caller, returning a handle from which the results are collected.
+  ///
+  /// # Errors
+  ///
+  /// The session thread could not be created.
+  ///
+  /// ```
+  /// extern crate apis;
+  ///
+  /// apis::def_session! {
+  ///   context Background {
+  ///     PROCESSES where
+  ///       let process    = self,
+  ///       let message_in = message_in
+  ///     [
+  ///       process Counter (count : u64) -> (u64) {
+  ///         kind { apis::process::Kind::Isochronous {
+  ///           tick:             std::time::Duration::from_millis (1),
+  ///           ticks_per_update: 1,
+  ///           sleep:            apis::process::Sleep::Thread,
+  ///           catch_up:         apis::process::CatchUp::All
+  ///         } }
+  ///         sourcepoints []
+  ///         endpoints    []
+  ///         terminate      { process.result = process.count }
+  ///         handle_message { unreachable!() }
+  ///         update {
+  ///           process.count += 1;
+  ///           if process.count < 10 {
+  ///             apis::process::ControlFlow::Continue
+  ///           } else {
+  ///             apis::process::ControlFlow::Break
+  ///           }
+  ///         }
+  ///       }
+  ///     ]
+  ///     CHANNELS  []
+  ///     MESSAGES  []
+  ///   }
+  /// }
+  ///
+  /// fn main() {
+  ///   use apis::session::Context;
+  ///   let session : apis::Session <Background> =
+  ///     Background::def().unwrap().into();
+  ///   let mut handle = session.spawn().unwrap();
+  ///   // do other work while the session runs
+  ///   let results = loop {
+  ///     if let Some (results) = handle.try_results() {
+  ///       break results
+  ///     }
+  ///     std::thread::sleep (std::time::Duration::from_millis (1));
+  ///   };
+  ///   assert!(handle.is_finished());
+  ///   let GlobalPresult::Counter (count) =
+  ///     results[ProcessId::Counter as usize];
+  ///   assert_eq!(count, 10);
+  /// }
+  /// ```
+  pub fn spawn (mut self) -> std::io::Result <SessionHandle <CTX>> where
+    Self       : Send,
+    CTX        : 'static,
+    CTX::GPRES : Send
+  {
+    let (results_tx, results_rx) = std::sync::mpsc::channel();
+    let controller      = self.controller();
+    let shutdown_handle = self.shutdown_handle();
+    let thread = std::thread::Builder::new()
+      .name (self.name().to_string())
+      .spawn (move || {
+        // the receiver is dropped if the handle was dropped
+        let _ = results_tx.send (self.run());
+      })?;
+    Ok (SessionHandle {
+      results_rx, thread: Some (thread), controller, shutdown_handle
+    })
+  }
+
+  /// Creates a new session and runs to completion, returning the outcome of
+  /// each process: either its result or the message of its panic.
+  ///
+  /// The channels of a panicking process are dropped, and unless the process
+  /// is restarted by its supervisor the session is shut down: the other
+  /// processes end as if shut down with `ShutdownHandle::shut_down`, calling
+  /// `terminate` and reporting their results.
+  ///
+  /// ```
+  /// extern crate apis;
+  ///
+  /// apis::def_session! {
+  ///   context Faulty {
+  ///     PROCESSES where
+  ///       let process    = self,
+  ///       let message_in = message_in
+  ///     [
+  ///       process Sender (sent : u8) {
+  ///         kind { apis::process::Kind::anisochronous_default() }
+  ///         sourcepoints [Values]
+  ///         endpoints    []
+  ///         handle_message { unreachable!() }
+  ///         update {
+  ///           assert!(process.sent < 3, "sender failed");
+  ///           process.sent += 1;
+  ///           process.send (ChannelId::Values, Value::Next).into()
+  ///         }
+  ///       }
+  ///       process Heartbeat (beats : u64) {
+  ///         kind { apis::process::Kind::Mesochronous {
+  ///           tick:             std::time::Duration::from_millis (1),
+  ///           ticks_per_update: 1,
+  ///           sleep:            apis::process::Sleep::Thread
+  ///         } }
+  ///         sourcepoints []
+  ///         endpoints    []
+  ///         handle_message { unreachable!() }
+  ///         update {
+  ///           process.beats += 1;
+  ///           apis::process::ControlFlow::Continue
+  ///         }
+  ///       }
+  ///       process Receiver (received : u8) -> (u8) {
+  ///         kind { apis::process::Kind::asynchronous_default() }
+  ///         sourcepoints []
+  ///         endpoints    [Values]
+  ///         terminate { process.result = process.received }
+  ///         handle_message {
+  ///           process.received += 1;
+  ///           apis::process::ControlFlow::Continue
+  ///         }
+  ///         update { apis::process::ControlFlow::Continue }
+  ///       }
+  ///     ]
+  ///     CHANNELS [
+  ///       channel Values <Value> (Simplex) {
+  ///         producers [Sender]
+  ///         consumers [Receiver]
+  ///       }
+  ///     ]
+  ///     MESSAGES [
+  ///       message Value { Next }
+  ///     ]
+  ///   }
+  /// }
+  ///
+  /// fn main() {
+  ///   use apis::session::Context;
+  ///   use apis::process::Outcome;
+  ///   let mut session : apis::Session <Faulty> = Faulty::def().unwrap().into();
+  ///   let outcomes = session.run_outcomes();
+  ///   assert!(matches!(&outcomes[ProcessId::Sender as usize],
+  ///     Outcome::Panicked (message) if message == "sender failed"));
+  ///   // the other processes are shut down, including the heartbeat which
+  ///   // would otherwise run forever
+  ///   assert!(matches!(outcomes[ProcessId::Receiver as usize],
+  ///     Outcome::Ended (GlobalPresult::Receiver (received)) if received <= 3));
+  ///   assert!(matches!(outcomes[ProcessId::Heartbeat as usize],
+  ///     Outcome::Ended (_)));
+  /// }
+  /// ```
+  pub fn run_outcomes (&mut self)
+    -> vec_map::VecMap <process::Outcome <CTX::GPRES>>
+  where
+    CTX : 'static
+  {
+    let channels = self.take_channels();
+    self.run_with_outcomes (channels, vec_map::VecMap::new(), None)
+  }
+
   /// Run a session with given channels and handles to processes that are
   /// running in a continuation from a previous session.
+  ///
+  /// Processes that panicked have no result.
   pub fn run_with (&mut self,
     channels        : vec_map::VecMap <channel::Channel <CTX>>,
     process_handles : vec_map::VecMap <process::Handle <CTX>>,
     main_process    : Option <Box <CTX::GPROC>>
   ) -> vec_map::VecMap <CTX::GPRES> {
+    results (self.run_with_outcomes (channels, process_handles, main_process))
+  }
+
+  /// As `run_with`, returning the outcome of each process.
+  pub fn run_with_outcomes (&mut self,
+    channels        : vec_map::VecMap <channel::Channel <CTX>>,
+    process_handles : vec_map::VecMap <process::Handle <CTX>>,
+    main_process    : Option <Box <CTX::GPROC>>
+  ) -> vec_map::VecMap <process::Outcome <CTX::GPRES>> {
     use process::Global;
 
     self.start (process_handles, channels, main_process);
     if let Some (ref mut main_gproc) = self.as_mut().main_process {
       main_gproc.run();
     }
-    let mut results = vec_map::VecMap::with_capacity (CTX::PID::COUNT);
-    for (pid, process_handle) in self.as_mut().process_handles.iter() {
-      assert!{
-        results.insert (pid, process_handle.result_rx.recv().unwrap()).is_none()
+    self.outcomes()
+  }
+
+  /// Creates a new session and runs it to completion on the current thread,
+  /// interleaving the processes in an order determined by the given seed.
+  ///
+  /// Instead of each process running its own loop on a spawned thread,
+  /// processes are advanced one step at a time (see `process::step`): on each
+  /// round the running processes are visited in a shuffled order until one of
+  /// them makes progress by handling a message, tick, timeout, or timer. When
+  /// none can, the session clock is advanced to the earliest deadline of any
+  /// process. The clock is replaced by a manual `process::clock::Virtual`
+  /// clock, so timed processes run without waiting and with exact tick
+  /// counts.
+  ///
+  /// The same seed always produces the same interleaving of message delivery
+  /// and updates, so a race between processes that shows up for one seed can
+  /// be reproduced, and a range of seeds can be run to search for one.
+  ///
+  /// As with `run`, processes that panicked have no result. Supervised
+  /// processes are not restarted, and control requests and shutdown (see
+  /// `controller` and `shutdown_handle`) are ignored.
+  ///
+  /// Processes must not block outside of the steps taken by the session:
+  /// a `send` on a full bounded channel or a blocking call in `update` blocks
+  /// the whole session.
+  ///
+  /// ```
+  /// extern crate apis;
+  ///
+  /// apis::def_session! {
+  ///   context Race {
+  ///     PROCESSES where
+  ///       let process    = self,
+  ///       let message_in = message_in
+  ///     [
+  ///       process A (sent : u8) {
+  ///         kind { apis::process::Kind::anisochronous_default() }
+  ///         sourcepoints [Letters]
+  ///         endpoints    []
+  ///         handle_message { unreachable!() }
+  ///         update {
+  ///           let _ = process.send (ChannelId::Letters, Letter::A);
+  ///           process.sent += 1;
+  ///           if process.sent < 3 {
+  ///             apis::process::ControlFlow::Continue
+  ///           } else {
+  ///             apis::process::ControlFlow::Break
+  ///           }
+  ///         }
+  ///       }
+  ///       process B (sent : u8) {
+  ///         kind { apis::process::Kind::anisochronous_default() }
+  ///         sourcepoints [Letters]
+  ///         endpoints    []
+  ///         handle_message { unreachable!() }
+  ///         update {
+  ///           let _ = process.send (ChannelId::Letters, Letter::B);
+  ///           process.sent += 1;
+  ///           if process.sent < 3 {
+  ///             apis::process::ControlFlow::Continue
+  ///           } else {
+  ///             apis::process::ControlFlow::Break
+  ///           }
+  ///         }
+  ///       }
+  ///       process Reader (letters : String) -> (String) {
+  ///         kind { apis::process::Kind::asynchronous_default() }
+  ///         sourcepoints []
+  ///         endpoints    [Letters]
+  ///         terminate { process.result = process.letters.clone() }
+  ///         handle_message {
+  ///           match message_in {
+  ///             GlobalMessage::Letter (Letter::A) => process.letters.push ('a'),
+  ///             GlobalMessage::Letter (Letter::B) => process.letters.push ('b')
+  ///           }
+  ///           apis::process::ControlFlow::Continue
+  ///         }
+  ///         update { apis::process::ControlFlow::Continue }
+  ///       }
+  ///     ]
+  ///     CHANNELS [
+  ///       channel Letters <Letter> (Sink) {
+  ///         producers [A, B]
+  ///         consumers [Reader]
+  ///       }
+  ///     ]
+  ///     MESSAGES [
+  ///       message Letter { A, B }
+  ///     ]
+  ///   }
+  /// }
+  ///
+  /// fn main() {
+  ///   use apis::session::Context;
+  ///   let letters = |seed| {
+  ///     let mut session : apis::Session <Race> = Race::def().unwrap().into();
+  ///     let mut results = session.run_deterministic (seed);
+  ///     let Some (GlobalPresult::Reader (letters)) =
+  ///       results.remove (ProcessId::Reader as usize) else { unreachable!() };
+  ///     letters
+  ///   };
+  ///   // the same seed reproduces the same interleaving
+  ///   assert_eq!(letters (7), letters (7));
+  ///   // different seeds explore different interleavings
+  ///   let orders = (0..16).map (letters).collect::<std::collections::BTreeSet <_>>();
+  ///   assert!(1 < orders.len());
+  /// }
+  /// ```
+  ///
+  /// # Panics
+  ///
+  /// Panics if every process is blocked on its channels with no deadline
+  /// pending.
+  pub fn run_deterministic (&mut self, seed : u64)
+    -> vec_map::VecMap <CTX::GPRES>
+  where
+    CTX : 'static
+  {
+    use process::{Clock, Global};
+    use process::step::Step;
+
+    let clock = process::clock::Virtual::new();
+    let mut channels = self.take_channels();
+    let mut running  = vec_map::VecMap::with_capacity (CTX::PID::COUNT);
+    { // create all processes without spawning
+      let extended_state = self.as_mut();
+      extended_state.clock = std::sync::Arc::new (clock.clone());
+      for (pid, process_def) in extended_state.def.process_def.iter() {
+        let (inner, process_handle) =
+          create_process (process_def, &mut channels, &extended_state.clock,
+            &extended_state.supervisor);
+        assert!(running.insert (pid, process::Id::gproc (inner)).is_none());
+        assert!(extended_state.process_handles.insert (pid, process_handle)
+          .is_none());
       }
     }
+    self.handle_event (EventParams::Run{}.into()).unwrap();
+    log::debug!(session=self.name(), seed; "session started deterministic");
+
+    let mut running : vec_map::VecMap <(CTX::GPROC, _)> = running.into_iter()
+      .filter_map (|(pid, mut gproc)|
+        gproc.start_steps().map (|stepper| (pid, (gproc, stepper)))
+      ).collect();
+    let mut rng        = Rng (seed);
+    let mut order      = Vec::with_capacity (running.len());
+    let mut idle_round = false;
+    while !running.is_empty() {
+      order.clear();
+      order.extend (running.keys());
+      rng.shuffle (&mut order);
+      let mut progress = false;
+      let mut idle     = false;
+      let mut deadline = None;
+      for pid in order.iter().copied() {
+        let (gproc, stepper) = running.get_mut (pid).unwrap();
+        match gproc.step (stepper) {
+          Step::Progress => progress = true,
+          Step::Idle     => idle = true,
+          Step::Blocked (wake) =>
+            deadline = deadline.into_iter().chain (wake).min(),
+          Step::Ended    => {
+            let (mut gproc, stepper) = running.remove (pid).unwrap();
+            gproc.finish_steps (stepper);
+            progress = true;
+          }
+          Step::Panicked => {
+            drop (running.remove (pid));
+            progress = true;
+          }
+        }
+        if progress {
+          break
+        }
+      }
+      if progress {
+        idle_round = false;
+        continue
+      }
+      // an idle update may have sent a message to a process that was visited
+      // earlier in the round, so time only advances after a second idle round
+      if idle && !idle_round {
+        idle_round = true;
+        continue
+      }
+      idle_round = false;
+      if let Some (deadline) = deadline {
+        clock.advance (deadline.saturating_duration_since (clock.now()));
+      } else {
+        assert!(idle, "run deterministic: every process is blocked on a channel");
+      }
+    }
+    results (self.outcomes())
+  }
+
+  /// Channels created when injectors or observers were taken, otherwise new
+  /// channels.
+  fn take_channels (&mut self) -> vec_map::VecMap <channel::Channel <CTX>>
+    where CTX : 'static
+  {
+    let extended_state = self.as_mut();
+    extended_state.channels.take()
+      .unwrap_or_else (|| extended_state.def.create_channels())
+  }
+
+  /// Wait for the outcome of every process and end the session.
+  fn outcomes (&mut self) -> vec_map::VecMap <process::Outcome <CTX::GPRES>> {
+    let mut outcomes = vec_map::VecMap::with_capacity (CTX::PID::COUNT);
+    for (pid, process_handle) in self.as_mut().process_handles.iter() {
+      // the result sender is only dropped without sending if the process
+      // thread died outside of the process run
+      let outcome = process_handle.result_rx.recv().unwrap_or_else (|_|
+        process::Outcome::Panicked ("process exited without a result".into()));
+      assert!(outcomes.insert (pid, outcome).is_none());
+    }
     self.handle_event (EventParams::End{}.into()).unwrap();
-    results
+    outcomes
   }
 
   /// Spawn processes.
@@ -298,35 +941,18 @@ impl <CTX : Context> Session <CTX> {
 
     { // spawn processes not found in input process handles
       let extended_state = self.as_mut();
+      // register processes that wait on the clock before any are spawned,
+      // including processes continued from a previous session
+      for process_def in extended_state.def.process_def.values() {
+        if process_def.is_clocked() {
+          extended_state.clock.register();
+        }
+      }
       for (pid, process_def) in extended_state.def.process_def.iter() {
         let process_handle = process_handles.remove (pid).unwrap_or_else (||{
-          // peer channels
-          let mut sourcepoints
-            : vec_map::VecMap <Box <dyn channel::Sourcepoint <CTX>>>
-            = vec_map::VecMap::new();
-          let mut endpoints
-            : vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>
-            = vec_map::VecMap::new();
-          for (cid, channel) in channels.iter_mut() {
-            if let Some (sourcepoint) = channel.sourcepoints.remove (pid) {
-              assert!(sourcepoints.insert (cid, sourcepoint).is_none());
-            }
-            if let Some (endpoint) = channel.endpoints.remove (pid) {
-              assert!(endpoints.insert (cid, endpoint).is_none());
-            }
-          }
-          // session control channels
-          let (result_tx, result_rx) = std::sync::mpsc::channel::<CTX::GPRES>();
-          let (continuation_tx, continuation_rx) =
-            std::sync::mpsc::channel::<Continuation <CTX>>();
-          // create the process
-          let session_handle = Handle::<CTX> { result_tx, continuation_rx };
-          let inner = process::Inner::new (process::inner::ExtendedState::new (
-            Some (process_def.clone()),
-            Some (session_handle),
-            Some (sourcepoints),
-            Some (std::cell::RefCell::new (Some (endpoints)))
-          ).unwrap());
+          let (inner, mut process_handle) =
+            create_process (process_def, &mut channels, &extended_state.clock,
+            &extended_state.supervisor);
           // if the process is the main process, only create it and don't spawn
           if let Some (main_process_id) = CTX::maybe_main()
             && *inner.as_ref().def.id() == main_process_id
@@ -335,17 +961,12 @@ impl <CTX : Context> Session <CTX> {
             // input since it should be accompanied by a process handle
             debug_assert!(main_process.is_none());
             main_process = Some (Box::new (process::Id::gproc (inner)));
-            return process::Handle {
-              result_rx, continuation_tx,
-              join_or_continue: either::Either::Right (None)
-            }
+            return process_handle
           }
           // spawn the process
-          let join_handle = process::Id::spawn (inner);
-          process::Handle {
-            result_rx, continuation_tx,
-            join_or_continue: either::Either::Left (join_handle)
-          }
+          process_handle.join_or_continue =
+            either::Either::Left (process::Id::spawn (inner));
+          process_handle
         });
         // store the process handle
         assert!(extended_state.process_handles.insert (pid, process_handle).is_none());
@@ -379,6 +1000,25 @@ impl <CTX : Context> Session <CTX> {
   }
 } // end impl Session
 
+impl Rng {
+  const fn next (&mut self) -> u64 {
+    self.0 = self.0.wrapping_add (0x9e37_79b9_7f4a_7c15);
+    let mut z = self.0;
+    z = (z ^ (z >> 30)).wrapping_mul (0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul (0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+
+  /// Fisher-Yates shuffle.
+  fn shuffle <T> (&mut self, items : &mut [T]) {
+    for i in (1..items.len()).rev() {
+      #[expect(clippy::cast_possible_truncation)]
+      let j = (self.next() % (i as u64 + 1)) as usize;
+      items.swap (i, j);
+    }
+  }
+}
+
 impl <CTX : Context> std::fmt::Debug for Session <CTX> {
   fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
     write!(f, "{}({:?})", self.name(), self.state_id())
@@ -387,12 +1027,25 @@ impl <CTX : Context> std::fmt::Debug for Session <CTX> {
 
 
 impl <CTX : Context> Def <CTX> {
-  pub fn create_channels (&self) -> vec_map::VecMap <channel::Channel <CTX>> {
+  /// Create the channels of the session.
+  ///
+  /// Channels consumed by an asynchronous process, or by an anisochronous
+  /// process that parks when idle, notify that process
+  /// when a message is sent or a sourcepoint disconnects (see
+  /// `channel::select`).
+  pub fn create_channels (&self) -> vec_map::VecMap <channel::Channel <CTX>>
+    where CTX : 'static
+  {
+    let notifiers = self.process_def.iter()
+      .filter (|(_, process_def)| process_def.is_notified())
+      .map (|(pid, _)| (pid, channel::select::Notifier::new()))
+      .collect();
     let mut channels = vec_map::VecMap::new();
     for (cid, channel_def) in self.channel_def.iter() {
       debug_assert_eq!(cid, channel_def.id().clone().into());
-      assert!(channels.insert (cid, channel::Id::create (channel_def.clone()))
-        .is_none());
+      let mut channel = channel::Id::create (channel_def.clone());
+      channel::select::notify_endpoints (&mut channel, &notifiers);
+      assert!(channels.insert (cid, channel).is_none());
     }
     channels
   }
@@ -467,6 +1120,17 @@ impl <CTX : Context> Def <CTX> {
         let endpoints   = &mut endpoints_from_channels[pid];
         endpoints.push (channel_id.clone());
       }
+      // both sides of a bidirectional channel send and receive
+      if channel_def.kind().is_bidirectional() {
+        for producer_id in channel_def.producers().iter() {
+          let pid : usize = producer_id.clone().into();
+          endpoints_from_channels[pid].push (channel_id.clone());
+        }
+        for consumer_id in channel_def.consumers().iter() {
+          let pid : usize = consumer_id.clone().into();
+          sourcepoints_from_channels[pid].push (channel_id.clone());
+        }
+      }
     }
 
     // compare the resulting sourcepoint and endpoint vec maps against those
@@ -640,8 +1304,12 @@ impl <CTX : Context> Def <CTX> {
       let consumers      = channel_def.consumers();
       let kind           = channel_def.kind();
       let local_type     = channel_local_types[cid];
-      let channel_string = escape (format!("{channel_id:?} <{local_type}>"));
-      match *kind {
+      let channel_string = if let Some (capacity) = channel_def.capacity() {
+        escape (format!("{channel_id:?} <{local_type}> [{capacity}]"))
+      } else {
+        escape (format!("{channel_id:?} <{local_type}>"))
+      };
+      match kind {
         channel::Kind::Simplex => {
           debug_assert_eq!(producers.len(), 1);
           debug_assert_eq!(consumers.len(), 1);
@@ -651,6 +1319,27 @@ impl <CTX : Context> Def <CTX> {
             consumers[0],
             channel_string).as_str());
         }
+        channel::Kind::Session (protocol) => {
+          debug_assert_eq!(producers.len(), 1);
+          debug_assert_eq!(consumers.len(), 1);
+          s.push_str (format!(
+            "    {:?} -> {:?} [dir=both,\
+           \n      label=<<FONT FACE=\"Sans Italic\">{}<BR/>{}</FONT>>]\n",
+            producers[0],
+            consumers[0],
+            channel_string,
+            escape (protocol.to_string())).as_str());
+        }
+        channel::Kind::Buffer => {
+          debug_assert_eq!(producers.len(), 1);
+          debug_assert_eq!(consumers.len(), 1);
+          s.push_str (format!(
+            "    {:?} -> {:?} [style=dashed,\
+           \n      label=<<FONT FACE=\"Sans Italic\">{}</FONT>>]\n",
+            producers[0],
+            consumers[0],
+            channel_string).as_str());
+        }
         channel::Kind::Source => {
           debug_assert_eq!(producers.len(), 1);
           // create a node
@@ -668,6 +1357,41 @@ impl <CTX : Context> Def <CTX> {
             ).as_str());
           }
         }
+        channel::Kind::Broadcast => {
+          debug_assert_eq!(producers.len(), 1);
+          // create a node
+          s.push_str (format!(
+            "    {channel_id:?} [label=<<B>*</B>>,\
+           \n      shape=diamond, style=\"\",\
+           \n      xlabel=<<FONT FACE=\"Sans Italic\">{channel_string}</FONT>>]\n").as_str());
+          // edges
+          s.push_str (format!(
+            "    {:?} -> {:?} []\n", producers[0], channel_id
+          ).as_str());
+          for consumer in consumers.as_slice() {
+            s.push_str (format!(
+              "    {channel_id:?} -> {consumer:?} [style=bold]\n"
+            ).as_str());
+          }
+        }
+        channel::Kind::Pool => {
+          // create a node
+          s.push_str (format!(
+            "    {channel_id:?} [label=<<B>=</B>>,\n      \
+                shape=diamond, style=\"\",\n      \
+                xlabel=<<FONT FACE=\"Sans Italic\">{channel_string}</FONT>>]\n").as_str());
+          // edges
+          for producer in producers.as_slice() {
+            s.push_str (format!(
+              "    {producer:?} -> {channel_id:?} []\n"
+            ).as_str());
+          }
+          for consumer in consumers.as_slice() {
+            s.push_str (format!(
+              "    {channel_id:?} -> {consumer:?} []\n"
+            ).as_str());
+          }
+        }
         channel::Kind::Sink => {
           debug_assert_eq!(consumers.len(), 1);
           // create a node
@@ -696,12 +1420,141 @@ impl <CTX : Context> Def <CTX> {
   } // end fn session_dotfile
 } // end impl Def
 
+impl <CTX : Context> Controller <CTX> {
+  /// Pause a process at the start of its next run loop iteration. A paused
+  /// process handles no messages, ticks, or timers until it is resumed, and
+  /// its pending timers are delayed by the time spent paused. A process
+  /// blocked on its endpoints is woken to pause.
+  pub fn pause (&self, pid : CTX::PID) {
+    log::debug!(process:?=pid; "session pause process");
+    self.supervisor.pause (pid.into())
+  }
+
+  /// Resume a paused process, or cancel a pause that has not taken effect.
+  pub fn resume (&self, pid : CTX::PID) {
+    log::debug!(process:?=pid; "session resume process");
+    self.supervisor.resume (pid.into())
+  }
+
+  /// Abort a process at the start of its next run loop iteration, or
+  /// immediately if it is paused. The process ends without calling
+  /// `terminate` and is reported as `process::Outcome::Aborted`; its channels
+  /// are dropped and it is not restarted by supervision.
+  pub fn abort (&self, pid : CTX::PID) {
+    log::debug!(process:?=pid; "session abort process");
+    self.supervisor.abort (pid.into())
+  }
+}
+
+impl <CTX : Context> SessionHandle <CTX> {
+  /// True once every process has ended and the results are available, or
+  /// have already been collected.
+  pub fn is_finished (&self) -> bool {
+    self.thread.as_ref().is_none_or (std::thread::JoinHandle::is_finished)
+  }
+
+  /// The results of the session if it has finished, without blocking.
+  /// Results are returned only once.
+  pub fn try_results (&mut self) -> Option <vec_map::VecMap <CTX::GPRES>> {
+    match self.results_rx.try_recv() {
+      Ok (results) => Some (self.received (results)),
+      Err (std::sync::mpsc::TryRecvError::Empty) => None,
+      Err (std::sync::mpsc::TryRecvError::Disconnected) => self.failed()
+    }
+  }
+
+  /// Block until the session finishes and return the results.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the results were already returned by `try_results` or
+  /// `join_timeout`, and resumes the panic of a session thread that
+  /// panicked.
+  pub fn join (mut self) -> vec_map::VecMap <CTX::GPRES> {
+    match self.results_rx.recv() {
+      Ok (results) => self.received (results),
+      Err (std::sync::mpsc::RecvError) => self.failed()
+        .expect ("session handle join: results already returned")
+    }
+  }
+
+  /// Block until the session finishes or the timeout elapses. Returns `None`
+  /// on timeout, or if the results were already returned.
+  pub fn join_timeout (&mut self, timeout : std::time::Duration)
+    -> Option <vec_map::VecMap <CTX::GPRES>>
+  {
+    match self.results_rx.recv_timeout (timeout) {
+      Ok (results) => Some (self.received (results)),
+      Err (std::sync::mpsc::RecvTimeoutError::Timeout) => None,
+      Err (std::sync::mpsc::RecvTimeoutError::Disconnected) => self.failed()
+    }
+  }
+
+  /// Control the processes of the running session (see
+  /// `Session::controller`).
+  pub const fn controller (&self) -> &Controller <CTX> {
+    &self.controller
+  }
+
+  /// Shut down the running session (see `Session::shutdown_handle`).
+  pub const fn shutdown_handle (&self) -> &ShutdownHandle {
+    &self.shutdown_handle
+  }
+
+  fn received (&mut self, results : vec_map::VecMap <CTX::GPRES>)
+    -> vec_map::VecMap <CTX::GPRES>
+  {
+    self.thread.take().unwrap().join().unwrap();
+    results
+  }
+
+  /// The session thread ended without sending results: either they were
+  /// already returned, or the thread panicked and the panic is resumed.
+  fn failed <T> (&mut self) -> Option <T> {
+    if let Some (Err (payload)) = self.thread.take().map (std::thread::JoinHandle::join) {
+      std::panic::resume_unwind (payload)
+    }
+    None
+  }
+}
+
+impl ShutdownHandle {
+  /// Ask every process of the session to end. Has no further effect when
+  /// called again.
+  pub fn shut_down (&self) {
+    log::info!("session shutdown requested");
+    self.supervisor.shut_down()
+  }
+
+  pub fn is_shut_down (&self) -> bool {
+    self.supervisor.is_shut_down()
+  }
+
+  /// Shut down the session when the process receives `SIGINT` or `SIGTERM`.
+  /// The signal handlers are installed by the first registration, and each
+  /// signal shuts down every session registered since the previous signal.
+  /// A signal received when no registered session is left to shut down,
+  /// such as a second `SIGINT`, restores the signal actions in place before
+  /// the first registration and is raised again with its previous effect.
+  ///
+  /// # Errors
+  ///
+  /// Returns the OS error if the signal handlers cannot be installed.
+  #[cfg(all(feature = "signal", target_os = "linux"))]
+  pub fn shut_down_on_signals (&self) -> std::io::Result <()> {
+    signal::register (self.clone())
+  }
+}
+
 impl <CTX : Context> From <Def <CTX>> for Session <CTX> {
   fn from (def : Def <CTX>) -> Self {
     Self::new (ExtendedState::new (
       Some (def),
       Some (vec_map::VecMap::new()),
-      Some (None)
+      Some (None),
+      None,
+      None,
+      None
     ).unwrap())
   }
 }
@@ -717,6 +1570,71 @@ pub fn report_sizes <CTX : Context> () {
   println!("...session report sizes");
 }
 
+/// Results of the processes that ended normally.
+fn results <GPRES> (outcomes : vec_map::VecMap <process::Outcome <GPRES>>)
+  -> vec_map::VecMap <GPRES>
+{
+  outcomes.into_iter().filter_map (|(pid, outcome)| match outcome {
+    process::Outcome::Ended (result) => Some ((pid, result)),
+    process::Outcome::Panicked (_) | process::Outcome::Aborted => None
+  }).collect()
+}
+
+/// Create a process holding its peer channels, which are removed from the
+/// given channels, and return it with a handle for a process that is not
+/// spawned.
+fn create_process <CTX : Context> (
+  process_def : &process::Def <CTX>,
+  channels    : &mut vec_map::VecMap <channel::Channel <CTX>>,
+  clock       : &std::sync::Arc <dyn process::Clock>,
+  supervisor  : &std::sync::Arc <process::Supervisor>
+) -> (process::Inner <CTX>, process::Handle <CTX>) {
+  let pid = process_def.id().clone().into();
+  // peer channels
+  let mut sourcepoints
+    : vec_map::VecMap <Box <dyn channel::Sourcepoint <CTX>>>
+    = vec_map::VecMap::new();
+  let mut endpoints
+    : vec_map::VecMap <Box <dyn channel::Endpoint <CTX>>>
+    = vec_map::VecMap::new();
+  for (cid, channel) in channels.iter_mut() {
+    if let Some (sourcepoint) = channel.sourcepoints.remove (pid) {
+      assert!(sourcepoints.insert (cid, sourcepoint).is_none());
+    }
+    if let Some (endpoint) = channel.endpoints.remove (pid) {
+      assert!(endpoints.insert (cid, endpoint).is_none());
+    }
+  }
+  // a process blocked on its endpoints is woken by control requests and
+  // one-for-all restarts
+  if let Some (notifier) = endpoints.values().find_map (|e| e.notifier()) {
+    supervisor.add_notifier (pid, notifier.clone());
+  }
+  // session control channels
+  let (result_tx, result_rx) =
+    std::sync::mpsc::channel::<process::Outcome <CTX::GPRES>>();
+  let (continuation_tx, continuation_rx) =
+    std::sync::mpsc::channel::<Continuation <CTX>>();
+  // create the process
+  let session_handle = Handle::<CTX> { result_tx, continuation_rx };
+  let inner = process::Inner::new (process::inner::ExtendedState::new (
+    Some (process_def.clone()),
+    Some (session_handle),
+    Some (sourcepoints),
+    Some (std::cell::RefCell::new (Some (endpoints))),
+    None,
+    None,
+    Some (clock.clone()),
+    Some (supervisor.clone()),
+    None
+  ).unwrap());
+  let process_handle = process::Handle {
+    result_rx, continuation_tx,
+    join_or_continue: either::Either::Right (None)
+  };
+  (inner, process_handle)
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 //  test mock                                                                 //
 ////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,171 @@
+//! Shutdown of sessions on `SIGINT` and `SIGTERM`.
+//!
+//! The signal handler only writes the signal number to a pipe, which is
+//! async-signal-safe. A watcher thread reads from the pipe and shuts down the
+//! sessions registered with `ShutdownHandle::shut_down_on_signals`. When no
+//! registered session is left to shut down, the watcher restores the previous
+//! signal actions and raises the signal again, so that for example a second
+//! `SIGINT` terminates the program as usual.
+
+use std::io;
+use std::sync::{Mutex, Weak};
+use std::sync::atomic::{AtomicI32, Ordering};
+use crate::process;
+use super::ShutdownHandle;
+
+/// Signals handled while a session is registered
+const SIGNALS : [libc::c_int; 2] = [libc::SIGINT, libc::SIGTERM];
+
+/// Write end of the pipe read by the watcher thread, set once the pipe is
+/// created and kept open for later installations of the signal handlers
+static PIPE_WRITE : AtomicI32 = AtomicI32::new (-1);
+/// Actions replaced by the signal handlers while they are installed; held
+/// while installing or restoring the signal handlers
+static INSTALL    : Mutex <Option <[libc::sigaction; 2]>> = Mutex::new (None);
+/// Supervisors of the sessions shut down by the next signal; held weakly so
+/// that registered sessions are freed when they end
+static HANDLES    : Mutex <Vec <Weak <process::Supervisor>>> =
+  Mutex::new (Vec::new());
+
+///////////////////////////////////////////////////////////////////////////////
+//  functions
+///////////////////////////////////////////////////////////////////////////////
+
+/// Shut down the session of the given handle on the next signal, installing
+/// the signal handlers if they are not installed.
+pub(super) fn register (handle : ShutdownHandle) -> io::Result <()> {
+  // the install lock is taken first so that the watcher does not restore the
+  // previous actions after this registration
+  let mut installed = INSTALL.lock().unwrap();
+  let mut handles = HANDLES.lock().unwrap();
+  handles.retain (|supervisor| supervisor.strong_count() > 0);
+  handles.push (std::sync::Arc::downgrade (&handle.supervisor));
+  drop (handles);
+  let result = if installed.is_none() {
+    install().map (|previous| *installed = Some (previous))
+  } else {
+    Ok (())
+  };
+  drop (installed);
+  result
+}
+
+/// Install the signal handlers, creating the pipe and the watcher thread if
+/// this is the first installation, and return the replaced actions.
+fn install() -> io::Result <[libc::sigaction; 2]> {
+  if PIPE_WRITE.load (Ordering::SeqCst) < 0 {
+    open_pipe()?;
+  }
+  // SAFETY: an all-zero `sigaction` is a valid value with an empty mask
+  let mut previous : [libc::sigaction; 2] = unsafe { std::mem::zeroed() };
+  for (i, signal) in SIGNALS.into_iter().enumerate() {
+    // SAFETY: an all-zero `sigaction` is a valid value with an empty mask
+    let mut action : libc::sigaction = unsafe { std::mem::zeroed() };
+    action.sa_sigaction = handle_signal as extern "C" fn (libc::c_int)
+      as libc::sighandler_t;
+    action.sa_flags = libc::SA_RESTART;
+    // SAFETY: the handler only calls async-signal-safe functions
+    if unsafe {
+      libc::sigaction (signal, &raw const action, &raw mut previous[i])
+    } != 0 {
+      let error = io::Error::last_os_error();
+      restore (&previous[..i]);
+      return Err (error)
+    }
+  }
+  Ok (previous)
+}
+
+/// Restore the actions replaced by the signal handlers, in the order of
+/// `SIGNALS`.
+fn restore (previous : &[libc::sigaction]) {
+  for (signal, action) in SIGNALS.into_iter().zip (previous) {
+    // SAFETY: the action was returned by `sigaction`
+    unsafe { libc::sigaction (signal, action, std::ptr::null_mut()); }
+  }
+}
+
+/// Create the pipe written by the signal handler and spawn the watcher thread
+/// reading from it.
+fn open_pipe() -> io::Result <()> {
+  let mut fds = [0; 2];
+  // SAFETY: the pointer is to an array of two file descriptors
+  if unsafe { libc::pipe2 (fds.as_mut_ptr(), libc::O_CLOEXEC) } != 0 {
+    return Err (io::Error::last_os_error())
+  }
+  let [read_fd, write_fd] = fds;
+  // a full pipe must not block the signal handler
+  // SAFETY: the file descriptor is open
+  if unsafe {
+    libc::fcntl (write_fd, libc::F_SETFL, libc::O_NONBLOCK)
+  } != 0 {
+    let error = io::Error::last_os_error();
+    close (read_fd);
+    close (write_fd);
+    return Err (error)
+  }
+  if let Err (error) = std::thread::Builder::new()
+    .name ("apis-signal".to_string())
+    .spawn (move || watch (read_fd))
+  {
+    close (read_fd);
+    close (write_fd);
+    return Err (error)
+  }
+  PIPE_WRITE.store (write_fd, Ordering::SeqCst);
+  Ok (())
+}
+
+extern "C" fn handle_signal (signal : libc::c_int) {
+  #[expect(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+  let byte = signal as u8;
+  // SAFETY: `errno` of the interrupted code is saved and restored, and
+  // `write` is async-signal-safe; the write end is non-blocking, so if the
+  // pipe is full the byte is dropped and the watcher has already been woken
+  unsafe {
+    let errno = *libc::__errno_location();
+    libc::write (PIPE_WRITE.load (Ordering::Relaxed), (&raw const byte).cast(),
+      1);
+    *libc::__errno_location() = errno;
+  }
+}
+
+fn close (fd : libc::c_int) {
+  // SAFETY: the file descriptor is open and not used after closing
+  unsafe { libc::close (fd); }
+}
+
+/// Shut down the registered sessions on each signal read from the pipe, or
+/// give the signal its previous effect if no registered session is left.
+fn watch (read_fd : libc::c_int) {
+  let mut byte = 0u8;
+  loop {
+    // SAFETY: the buffer is a single byte
+    let count = unsafe { libc::read (read_fd, (&raw mut byte).cast(), 1) };
+    if count == 1 {
+      log::info!(signal=byte; "session shutdown signal received");
+      let mut installed = INSTALL.lock().unwrap();
+      let supervisors = std::mem::take (&mut *HANDLES.lock().unwrap())
+        .iter().filter_map (Weak::upgrade).collect::<Vec <_>>();
+      if supervisors.is_empty() {
+        if let Some (previous) = installed.take() {
+          restore (&previous);
+        }
+        drop (installed);
+        log::info!(signal=byte; "no session to shut down: raising signal");
+        // SAFETY: the previous action of the signal is restored
+        unsafe { libc::raise (byte.into()); }
+      } else {
+        drop (installed);
+        for supervisor in supervisors {
+          ShutdownHandle { supervisor }.shut_down();
+        }
+      }
+    } else if count == 0
+      || io::Error::last_os_error().kind() != io::ErrorKind::Interrupted
+    {
+      break
+    }
+  }
+  close (read_fd);
+}